@@ -0,0 +1,40 @@
+//! Simulated 16x2 panel drawn in the terminal.
+//!
+//! cargo run --example terminal --features mock
+
+use core::fmt::Write;
+use st7032i::mock::Device;
+use st7032i::terminal::{StdDelay, Terminal};
+use st7032i::{Direction, DisplaySize, ST7032i};
+use std::io;
+use std::thread;
+use std::time::Duration;
+
+const HEART: [u8; 8] = [0x00, 0x0a, 0x1f, 0x1f, 0x0e, 0x04, 0x00, 0x00];
+const BELL: [u8; 8] = [0x04, 0x0e, 0x0e, 0x0e, 0x1f, 0x00, 0x04, 0x00];
+
+fn main() {
+    let size = DisplaySize::new(16, 2).unwrap();
+    let terminal = Terminal::new(Device::new(size), io::stdout());
+    let mut display = ST7032i::new(terminal, StdDelay, size);
+
+    display.init().unwrap();
+    display.create_char(0, HEART).unwrap();
+    display.create_char(1, BELL).unwrap();
+    display.move_cursor(0, 0).unwrap();
+    write!(display, "Hello, \u{0}\u{1}").unwrap();
+    display.move_cursor(1, 0).unwrap();
+    write!(display, "ｺﾝﾆﾁﾊ 25°C").unwrap();
+    display.show_cursor(true).unwrap();
+    thread::sleep(Duration::from_secs(2));
+
+    display.hide_cursor().unwrap();
+    for _ in 0..8 {
+        display.shift_display(Direction::RightToLeft).unwrap();
+        thread::sleep(Duration::from_millis(300));
+    }
+    for _ in 0..8 {
+        display.shift_display(Direction::LeftToRigh).unwrap();
+        thread::sleep(Duration::from_millis(300));
+    }
+}
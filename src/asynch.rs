@@ -0,0 +1,249 @@
+//! Async driver based on the `embedded-hal-async` traits, mirrors the
+//! blocking `ST7032i` API over I²C.
+
+use crate::command::{self, InstructionSet, CONTROL_COMMAND, DATA_CHUNK, I2C_BYTE_NS};
+use crate::{Config, Direction, DisplaySize, Error, I2C_ADRESS};
+use embedded_hal_async::delay::DelayNs;
+use embedded_hal_async::i2c::I2c;
+
+/// Async driver for the ST7032i
+#[derive(Debug)]
+pub struct ST7032iAsync<I2C, D> {
+    i2c: I2C,
+    delay: D,
+    address: u8,
+    size: DisplaySize,
+    display: bool,
+    cursor: bool,
+    blink: bool,
+    config: Config,
+    fallback: Option<u8>,
+    /// DDRAM address counter
+    ac: u8,
+}
+
+impl<I2C, E, D> ST7032iAsync<I2C, D>
+where
+    I2C: I2c<Error = E>,
+    D: DelayNs,
+{
+    /// Initialize the ST7032i driver.
+    pub fn new(i2c: I2C, delay: D, size: DisplaySize) -> Self {
+        Self::with_config(i2c, delay, size, Config::default())
+    }
+
+    /// Initialize the ST7032i driver with custom power settings.
+    pub fn with_config(i2c: I2C, delay: D, size: DisplaySize, config: Config) -> Self {
+        ST7032iAsync {
+            i2c,
+            delay,
+            address: I2C_ADRESS,
+            size,
+            display: false,
+            cursor: false,
+            blink: false,
+            config,
+            fallback: Some(b'?'),
+            ac: 0,
+        }
+    }
+
+    /// Use I²C address other than the default `I2C_ADRESS`
+    pub fn with_address(mut self, address: u8) -> Self {
+        self.address = address;
+        self
+    }
+
+    /// Destroy driver instance, return I²C bus and delay instance
+    pub fn release(self) -> (I2C, D) {
+        (self.i2c, self.delay)
+    }
+
+    /// Display geometry
+    pub fn size(&self) -> DisplaySize {
+        self.size
+    }
+
+    /// Initialize the display.
+    pub async fn init(&mut self) -> Result<(), Error<E>> {
+        let lines = self.config.lines(self.size);
+        let entry = command::entry_mode(Direction::LeftToRigh, false);
+        let display = command::display_control(true, self.cursor, self.blink);
+        let sequence = command::init_sequence(&self.config, lines, false, entry, display);
+        for (n, (command, delay_us)) in sequence.iter().enumerate() {
+            match self.send_command(*command).await {
+                Err(_) if n == 0 => {
+                    self.delay.delay_us(command::INIT_RETRY_US).await;
+                    continue;
+                }
+                result => result?,
+            }
+            if *delay_us > 0 {
+                self.delay.delay_us(*delay_us).await;
+            }
+        }
+        self.display = true;
+
+        self.clear().await
+    }
+
+    /// Switch display on
+    pub async fn on(&mut self) -> Result<(), Error<E>> {
+        self.display = true;
+        self.send_display_mode().await
+    }
+
+    /// Switch display off
+    pub async fn off(&mut self) -> Result<(), Error<E>> {
+        self.display = false;
+        self.send_display_mode().await
+    }
+
+    /// Clear all the display data and set DDRAM address to "00H".
+    pub async fn clear(&mut self) -> Result<(), Error<E>> {
+        self.send_instruction(command::CLEAR_DISPLAY, self.config.clear_delay_us())
+            .await?;
+        self.ac = 0;
+        Ok(())
+    }
+
+    /// Set DDRAM address to "0" and return cursor to its original position if shifted.
+    pub async fn home(&mut self) -> Result<(), Error<E>> {
+        self.send_instruction(command::RETURN_HOME, self.config.clear_delay_us())
+            .await?;
+        self.ac = 0;
+        Ok(())
+    }
+
+    /// Move cursor to specified location
+    pub async fn move_cursor(&mut self, row: u8, col: u8) -> Result<(), Error<E>> {
+        if row > 0 && self.config.double_height {
+            return Err(Error::OutOfRange);
+        }
+        let address = self.size.address(row, col).ok_or(Error::OutOfRange)?;
+        self.send_command(command::ddram_address(address)).await?;
+        self.ac = address;
+        Ok(())
+    }
+
+    /// Set display contrast (0..=63)
+    pub async fn set_contrast(&mut self, contrast: u8) -> Result<(), Error<E>> {
+        if contrast > 63 {
+            return Err(Error::InvalidArgument);
+        }
+        self.config.contrast = contrast;
+        let lines = self.config.lines(self.size);
+        self.send_function(InstructionSet::Extented, lines).await?;
+        for command in command::contrast_setup(&self.config, false).iter() {
+            self.send_command(*command).await?;
+        }
+        self.send_function(InstructionSet::Normal, lines).await
+    }
+
+    /// Show cursor
+    pub async fn show_cursor(&mut self, blink: bool) -> Result<(), Error<E>> {
+        self.cursor = true;
+        self.blink = blink;
+        self.send_display_mode().await
+    }
+
+    /// Hide cursor
+    pub async fn hide_cursor(&mut self) -> Result<(), Error<E>> {
+        self.cursor = false;
+        self.blink = false;
+        self.send_display_mode().await
+    }
+
+    /// Create custom character in CGRAM, the cursor position is restored
+    pub async fn create_char(&mut self, offset: u8, bitmap: [u8; 8]) -> Result<(), Error<E>> {
+        self.send_command(command::cgram_address(offset)).await?;
+        self.write_data(&bitmap).await?;
+        self.send_command(command::ddram_address(self.ac)).await
+    }
+
+    /// Set character code used for glyphs missing in the character ROM.
+    /// With `None` writing such a glyph fails with `Error::UnsupportedChar`.
+    pub fn set_fallback(&mut self, code: Option<u8>) {
+        self.fallback = code;
+    }
+
+    /// Write string at current cursor position
+    pub async fn write_str(&mut self, s: &str) -> Result<(), Error<E>> {
+        let mut chars = s.chars();
+        let mut buf = [0; DATA_CHUNK];
+        loop {
+            match command::encode_chunk(&mut chars, self.fallback, &mut buf)? {
+                0 => return Ok(()),
+                len => {
+                    self.write_data(&buf[..len]).await?;
+                    self.advance(len);
+                }
+            }
+        }
+    }
+
+    /// Write raw character codes at current cursor position
+    pub async fn write_bytes(&mut self, bytes: &[u8]) -> Result<(), Error<E>> {
+        self.write_data(bytes).await?;
+        self.advance(bytes.len());
+        Ok(())
+    }
+
+    /// Track the address counter over `count` written characters
+    fn advance(&mut self, count: usize) {
+        let lines = self.config.lines(self.size);
+        for _ in 0..count {
+            self.ac = command::next_address(self.ac, lines, Direction::LeftToRigh);
+        }
+    }
+
+    async fn send_display_mode(&mut self) -> Result<(), Error<E>> {
+        let command = command::display_control(self.display, self.cursor, self.blink);
+        self.send_command(command).await
+    }
+
+    async fn send_function(&mut self, is: InstructionSet, lines: u8) -> Result<(), Error<E>> {
+        let command = command::function_set(is, lines, self.config.double_height);
+        self.send_command(command).await
+    }
+
+    async fn send_command(&mut self, command: u8) -> Result<(), Error<E>> {
+        self.send_instruction(command, self.config.command_delay_us())
+            .await
+    }
+
+    async fn send_instruction(&mut self, command: u8, exec_us: u32) -> Result<(), Error<E>> {
+        self.i2c
+            .write(self.address, &[CONTROL_COMMAND, command])
+            .await
+            .map_err(Error::Bus)?;
+        self.delay.delay_us(exec_us).await;
+        Ok(())
+    }
+
+    /// Stream data when the controller keeps up with a 400 kHz bus,
+    /// otherwise send and wait for one byte at a time
+    async fn write_data(&mut self, data: &[u8]) -> Result<(), Error<E>> {
+        let exec_us = self.config.command_delay_us();
+        if self.config.keeps_up_with(I2C_BYTE_NS) {
+            for chunk in data.chunks(DATA_CHUNK) {
+                self.send_data(chunk).await?;
+            }
+            self.delay.delay_us(exec_us).await;
+        } else {
+            for byte in data {
+                self.send_data(&[*byte]).await?;
+                self.delay.delay_us(exec_us).await;
+            }
+        }
+        Ok(())
+    }
+
+    async fn send_data(&mut self, data: &[u8]) -> Result<(), Error<E>> {
+        let mut buf = [0; DATA_CHUNK + 1];
+        self.i2c
+            .write(self.address, command::data_frame(&mut buf, data))
+            .await
+            .map_err(Error::Bus)
+    }
+}
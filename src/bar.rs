@@ -0,0 +1,158 @@
+use crate::bus::Delay;
+use crate::charset::FULL_BLOCK;
+use crate::config::MAX_COLS;
+use crate::interface::Interface;
+use crate::{Error, Glyph, GlyphManager, ST7032i};
+
+/// Pixel columns in a character cell
+const CELL_WIDTH: u32 = 5;
+/// Pixel rows in a character cell
+const CELL_HEIGHT: u32 = 8;
+
+/// Cell with the `fill` leftmost pixel columns set
+const fn column_glyph(fill: u32) -> Glyph {
+    let row = (0x1f << (CELL_WIDTH - fill)) as u8 & 0x1f;
+    [row; 8]
+}
+
+/// Cell with the `fill` bottom pixel rows set
+const fn level_glyph(fill: u32) -> Glyph {
+    let mut glyph = [0; 8];
+    let mut row = 0;
+    while row < fill as usize {
+        glyph[7 - row] = 0x1f;
+        row += 1;
+    }
+    glyph
+}
+
+/// Scale `value` in `0..=max` to `0..=pixels`
+fn scale(value: u16, max: u16, pixels: u32) -> u32 {
+    if max == 0 {
+        return 0;
+    }
+    value.min(max) as u32 * pixels / max as u32
+}
+
+/// Horizontal bar graph with 5 steps per character cell.
+///
+/// Full cells use the ROM block character, the partially filled cell uses
+/// a custom character allocated by the `GlyphManager`.
+#[derive(Debug, Clone, Copy)]
+pub struct HorizontalBar {
+    row: u8,
+    col: u8,
+    width: u8,
+}
+
+impl HorizontalBar {
+    /// Bar `width` cells wide starting at specified location
+    pub fn new(row: u8, col: u8, width: u8) -> Self {
+        HorizontalBar { row, col, width }
+    }
+
+    /// Draw bar filled proportionally to `value` out of `max`
+    pub fn draw<DI, E, D>(
+        &self,
+        display: &mut ST7032i<DI, D>,
+        glyphs: &mut GlyphManager,
+        value: u16,
+        max: u16,
+    ) -> Result<(), Error<E>>
+    where
+        DI: Interface<Error = E>,
+        D: Delay,
+    {
+        let width = self.width as usize;
+        if self.row >= display.lines() || self.col as usize + width > display.size().cols as usize {
+            return Err(Error::OutOfRange);
+        }
+        let pixels = scale(value, max, self.width as u32 * CELL_WIDTH);
+        let full = (pixels / CELL_WIDTH) as usize;
+        let partial = pixels % CELL_WIDTH;
+
+        let mut cells = [b' '; MAX_COLS];
+        cells[..full].fill(FULL_BLOCK);
+        if partial > 0 {
+            cells[full] = glyphs.load(display, &column_glyph(partial))?;
+        }
+        display.move_cursor(self.row, self.col)?;
+        display.write_bytes(&cells[..width])
+    }
+}
+
+/// Vertical bar graph with 8 steps per character cell, one bar per column.
+///
+/// Useful for mini spectrum displays and sparklines. Partially filled cells
+/// use up to 7 custom characters allocated by the `GlyphManager`.
+#[derive(Debug, Clone, Copy)]
+pub struct VerticalBar {
+    row: u8,
+    col: u8,
+    width: u8,
+    height: u8,
+}
+
+impl VerticalBar {
+    /// Bars `height` rows tall in `width` columns, `row` is the top row
+    pub fn new(row: u8, col: u8, width: u8, height: u8) -> Self {
+        VerticalBar {
+            row,
+            col,
+            width,
+            height,
+        }
+    }
+
+    /// Draw one bar per value, filled proportionally to the value out of
+    /// `max`. Columns without value are cleared.
+    pub fn draw<DI, E, D>(
+        &self,
+        display: &mut ST7032i<DI, D>,
+        glyphs: &mut GlyphManager,
+        values: &[u16],
+        max: u16,
+    ) -> Result<(), Error<E>>
+    where
+        DI: Interface<Error = E>,
+        D: Delay,
+    {
+        let width = self.width as usize;
+        if self.col as usize + width > display.size().cols as usize
+            || self.row as usize + self.height as usize > display.lines() as usize
+        {
+            return Err(Error::OutOfRange);
+        }
+        let pixels = self.height as u32 * CELL_HEIGHT;
+        let fill = |col: usize, row: u8| {
+            let value = values
+                .get(col)
+                .map_or(0, |&value| scale(value, max, pixels));
+            let bottom = (self.height - 1 - row) as u32 * CELL_HEIGHT;
+            value.saturating_sub(bottom).min(CELL_HEIGHT)
+        };
+
+        let mut codes = [b' '; CELL_HEIGHT as usize + 1];
+        codes[CELL_HEIGHT as usize] = FULL_BLOCK;
+        let mut loaded = [false; CELL_HEIGHT as usize];
+        for row in 0..self.height {
+            for col in 0..width {
+                let level = fill(col, row) as usize;
+                if (1..CELL_HEIGHT as usize).contains(&level) && !loaded[level] {
+                    codes[level] = glyphs.load(display, &level_glyph(level as u32))?;
+                    loaded[level] = true;
+                }
+            }
+        }
+
+        let mut cells = [b' '; MAX_COLS];
+        for row in 0..self.height {
+            for (col, cell) in cells[..width].iter_mut().enumerate() {
+                *cell = codes[fill(col, row) as usize];
+            }
+            display.move_cursor(self.row + row, self.col)?;
+            display.write_bytes(&cells[..width])?;
+        }
+        Ok(())
+    }
+}
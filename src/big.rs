@@ -0,0 +1,50 @@
+//! Two rows tall digits built from 8 segment glyphs.
+
+/// Segment glyphs, referenced by their index in the cell tables
+pub(crate) const GLYPHS: [[u8; 8]; 8] = [
+    // 0: upper left corner
+    [0x07, 0x0f, 0x1f, 0x1f, 0x1f, 0x1f, 0x1f, 0x1f],
+    // 1: upper bar
+    [0x1f, 0x1f, 0x1f, 0x00, 0x00, 0x00, 0x00, 0x00],
+    // 2: upper right corner
+    [0x1c, 0x1e, 0x1f, 0x1f, 0x1f, 0x1f, 0x1f, 0x1f],
+    // 3: lower left corner
+    [0x1f, 0x1f, 0x1f, 0x1f, 0x1f, 0x1f, 0x0f, 0x07],
+    // 4: lower bar
+    [0x00, 0x00, 0x00, 0x00, 0x00, 0x1f, 0x1f, 0x1f],
+    // 5: lower right corner
+    [0x1f, 0x1f, 0x1f, 0x1f, 0x1f, 0x1f, 0x1e, 0x1c],
+    // 6: upper and middle bars
+    [0x1f, 0x1f, 0x1f, 0x00, 0x00, 0x00, 0x1f, 0x1f],
+    // 7: middle and lower bars
+    [0x1f, 0x00, 0x00, 0x00, 0x00, 0x1f, 0x1f, 0x1f],
+];
+
+/// Full block
+const F: u8 = crate::charset::FULL_BLOCK;
+/// Blank
+const S: u8 = b' ';
+/// Middle dot in the character ROM
+const DOT: u8 = 0xa5;
+
+/// Top and bottom row cells of a big character
+pub(crate) fn cells(c: char) -> Option<(&'static [u8], &'static [u8])> {
+    let cells: (&[u8], &[u8]) = match c {
+        '0' => (&[0, 1, 2], &[3, 4, 5]),
+        '1' => (&[1, 2, S], &[4, F, 4]),
+        '2' => (&[6, 6, 2], &[3, 4, 4]),
+        '3' => (&[6, 6, 2], &[4, 4, 5]),
+        '4' => (&[3, 4, F], &[S, S, F]),
+        '5' => (&[F, 6, 6], &[4, 4, 5]),
+        '6' => (&[0, 6, 6], &[3, 4, 5]),
+        '7' => (&[1, 1, 2], &[S, S, F]),
+        '8' => (&[0, 6, 2], &[3, 4, 5]),
+        '9' => (&[0, 6, 2], &[S, S, F]),
+        '-' => (&[4, 4, 4], &[S, S, S]),
+        ':' => (&[DOT], &[DOT]),
+        '.' => (&[S], b"."),
+        ' ' => (&[S], &[S]),
+        _ => return None,
+    };
+    Some(cells)
+}
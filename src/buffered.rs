@@ -0,0 +1,154 @@
+use crate::bus::Delay;
+use crate::charset;
+use crate::config::MAX_COLS;
+use crate::interface::Interface;
+use crate::{Error, ST7032i};
+use core::fmt;
+
+/// Buffered display, keeps a shadow copy of the visible DDRAM
+/// and sends only changed characters on `flush`.
+#[derive(Debug)]
+pub struct BufferedDisplay<DI, D> {
+    display: ST7032i<DI, D>,
+    /// Row major, no display has more than `MAX_COLS` cells
+    cells: [u8; MAX_COLS],
+    dirty: [bool; MAX_COLS],
+    row: u8,
+    col: u8,
+}
+
+impl<DI, E, D> BufferedDisplay<DI, D>
+where
+    DI: Interface<Error = E>,
+    D: Delay,
+{
+    /// Wrap initialized display, the first `flush` redraws the whole screen
+    pub fn new(display: ST7032i<DI, D>) -> Self {
+        BufferedDisplay {
+            display,
+            cells: [b' '; MAX_COLS],
+            dirty: [true; MAX_COLS],
+            row: 0,
+            col: 0,
+        }
+    }
+
+    /// Release the wrapped display
+    pub fn release(self) -> ST7032i<DI, D> {
+        self.display
+    }
+
+    /// Access the wrapped display. Call `invalidate` after changing
+    /// the screen content through it, e.g. with `clear` or `create_char`.
+    pub fn display(&mut self) -> &mut ST7032i<DI, D> {
+        &mut self.display
+    }
+
+    /// Mark all characters as changed, the next `flush` redraws the whole screen
+    pub fn invalidate(&mut self) {
+        self.dirty = [true; MAX_COLS];
+    }
+
+    /// Fill buffer with spaces and move cursor to the top left corner
+    pub fn clear(&mut self) {
+        let size = self.display.size;
+        for row in 0..self.display.lines() {
+            for col in 0..size.cols {
+                self.set(row, col, b' ');
+            }
+        }
+        self.row = 0;
+        self.col = 0;
+    }
+
+    /// Move buffer cursor to specified location
+    pub fn move_cursor(&mut self, row: u8, col: u8) -> Result<(), Error<E>> {
+        let size = self.display.size;
+        if row >= self.display.lines() || col >= size.cols {
+            return Err(Error::OutOfRange);
+        }
+        self.row = row;
+        self.col = col;
+        Ok(())
+    }
+
+    /// Put character code to specified location
+    pub fn set_char(&mut self, row: u8, col: u8, code: u8) -> Result<(), Error<E>> {
+        let size = self.display.size;
+        if row >= self.display.lines() || col >= size.cols {
+            return Err(Error::OutOfRange);
+        }
+        self.set(row, col, code);
+        Ok(())
+    }
+
+    /// Write string at current buffer cursor position,
+    /// text past the end of the row is clipped.
+    pub fn write_str(&mut self, s: &str) -> Result<(), Error<E>> {
+        for c in s.chars() {
+            let code = charset::encode(c)
+                .or(self.display.fallback)
+                .ok_or(Error::UnsupportedChar)?;
+            self.write_code(code);
+        }
+        Ok(())
+    }
+
+    /// Write raw character codes at current buffer cursor position,
+    /// codes past the end of the row are clipped.
+    pub fn write_bytes(&mut self, bytes: &[u8]) {
+        for code in bytes {
+            self.write_code(*code);
+        }
+    }
+
+    /// Send changed characters to the display
+    pub fn flush(&mut self) -> Result<(), Error<E>> {
+        let size = self.display.size;
+        for row in 0..self.display.lines() {
+            let line = row as usize * size.cols as usize;
+            let mut col = 0;
+            while col < size.cols {
+                if !self.dirty[line + col as usize] {
+                    col += 1;
+                    continue;
+                }
+                let start = col;
+                while col < size.cols && self.dirty[line + col as usize] {
+                    col += 1;
+                }
+                let run = line + start as usize..line + col as usize;
+                self.display.move_cursor(row, start)?;
+                self.display.write_bytes(&self.cells[run.clone()])?;
+                // Keep the run dirty on bus errors to resend it on next flush
+                self.dirty[run].fill(false);
+            }
+        }
+        Ok(())
+    }
+
+    fn write_code(&mut self, code: u8) {
+        if self.col < self.display.size.cols {
+            self.set(self.row, self.col, code);
+            self.col += 1;
+        }
+    }
+
+    fn set(&mut self, row: u8, col: u8, code: u8) {
+        let idx = row as usize * self.display.size.cols as usize + col as usize;
+        if self.cells[idx] != code {
+            self.cells[idx] = code;
+            self.dirty[idx] = true;
+        }
+    }
+}
+
+impl<DI, E, D> fmt::Write for BufferedDisplay<DI, D>
+where
+    DI: Interface<Error = E>,
+    D: Delay,
+{
+    fn write_str(&mut self, s: &str) -> fmt::Result {
+        BufferedDisplay::write_str(self, s).map_err(|_| fmt::Error)
+    }
+}
@@ -0,0 +1,211 @@
+//! Bus, pin and delay traits used by the driver.
+//!
+//! They are implemented for every type implementing the matching traits of
+//! the enabled `embedded-hal` version: 0.2 with the `eh02` feature (default)
+//! or 1.0 with the `embedded-hal-1` feature. The features are mutually
+//! exclusive.
+
+use core::cell::RefCell;
+
+/// I²C bus
+///
+/// The driver only ever writes to the display, so with embedded-hal 0.2
+/// implementing `blocking::i2c::Write` is enough. Features reading back
+/// from the controller should put their extra bounds on those methods only.
+pub trait I2cBus {
+    type Error;
+
+    /// Write bytes to the device at `address`
+    fn write(&mut self, address: u8, bytes: &[u8]) -> Result<(), Self::Error>;
+}
+
+/// I²C bus shared by several devices, e.g. displays at different addresses.
+///
+/// ```no_run
+/// use core::cell::RefCell;
+/// use linux_embedded_hal::{Delay, I2cdev};
+/// use st7032i::bus::SharedI2c;
+/// use st7032i::{DisplaySize, ST7032i};
+///
+/// # #[cfg(feature = "eh02")]
+/// # fn main() -> Result<(), Box<dyn std::error::Error>> {
+/// let bus = RefCell::new(I2cdev::new("/dev/i2c-1")?);
+/// let size = DisplaySize::new(16, 2).unwrap();
+/// let mut first = ST7032i::new(SharedI2c::new(&bus), Delay, size);
+/// let mut second = ST7032i::new(SharedI2c::new(&bus), Delay, size).with_address(0x3c);
+/// first.init().unwrap();
+/// second.init().unwrap();
+/// # Ok(())
+/// # }
+/// # #[cfg(not(feature = "eh02"))]
+/// # fn main() {}
+/// ```
+#[derive(Debug)]
+pub struct SharedI2c<'a, I2C> {
+    bus: &'a RefCell<I2C>,
+}
+
+impl<'a, I2C: I2cBus> SharedI2c<'a, I2C> {
+    /// Wrap bus shared through a `RefCell`
+    pub fn new(bus: &'a RefCell<I2C>) -> Self {
+        SharedI2c { bus }
+    }
+}
+
+impl<I2C: I2cBus> I2cBus for SharedI2c<'_, I2C> {
+    type Error = I2C::Error;
+
+    fn write(&mut self, address: u8, bytes: &[u8]) -> Result<(), Self::Error> {
+        self.bus.borrow_mut().write(address, bytes)
+    }
+}
+
+/// Mutably borrowed I²C bus, the bus is usable again once the driver
+/// is dropped or released.
+///
+/// ```no_run
+/// use linux_embedded_hal::{Delay, I2cdev};
+/// use st7032i::bus::BorrowedI2c;
+/// use st7032i::{DisplaySize, ST7032i};
+///
+/// # #[cfg(feature = "eh02")]
+/// # fn main() -> Result<(), Box<dyn std::error::Error>> {
+/// let mut bus = I2cdev::new("/dev/i2c-1")?;
+/// let size = DisplaySize::new(16, 2).unwrap();
+/// let mut display = ST7032i::new(BorrowedI2c::new(&mut bus), Delay, size);
+/// display.init().unwrap();
+/// display.release();
+/// // `bus` can talk to other peripherals again
+/// # Ok(())
+/// # }
+/// # #[cfg(not(feature = "eh02"))]
+/// # fn main() {}
+/// ```
+#[derive(Debug)]
+pub struct BorrowedI2c<'a, I2C> {
+    bus: &'a mut I2C,
+}
+
+impl<'a, I2C: I2cBus> BorrowedI2c<'a, I2C> {
+    /// Wrap mutably borrowed bus
+    pub fn new(bus: &'a mut I2C) -> Self {
+        BorrowedI2c { bus }
+    }
+}
+
+impl<I2C: I2cBus> I2cBus for BorrowedI2c<'_, I2C> {
+    type Error = I2C::Error;
+
+    fn write(&mut self, address: u8, bytes: &[u8]) -> Result<(), Self::Error> {
+        self.bus.write(address, bytes)
+    }
+}
+
+/// SPI bus, with embedded-hal 1.0 a `SpiDevice` handling chip select
+pub trait SpiBus {
+    type Error;
+
+    /// Write bytes to the device
+    fn write(&mut self, bytes: &[u8]) -> Result<(), Self::Error>;
+}
+
+/// Digital output pin
+pub trait OutputPin {
+    type Error;
+
+    /// Drive the pin high
+    fn set_high(&mut self) -> Result<(), Self::Error>;
+
+    /// Drive the pin low
+    fn set_low(&mut self) -> Result<(), Self::Error>;
+}
+
+/// Blocking delay
+pub trait Delay {
+    /// Pause execution for at least `us` microseconds
+    fn delay_us(&mut self, us: u32);
+}
+
+#[cfg(feature = "eh02")]
+mod eh02 {
+    use hal::blocking::delay::DelayUs;
+    use hal::blocking::i2c::Write;
+    use hal::blocking::spi;
+    use hal::digital::v2::OutputPin;
+
+    impl<T: Write> super::I2cBus for T {
+        type Error = T::Error;
+
+        fn write(&mut self, address: u8, bytes: &[u8]) -> Result<(), T::Error> {
+            Write::write(self, address, bytes)
+        }
+    }
+
+    impl<T: spi::Write<u8>> super::SpiBus for T {
+        type Error = T::Error;
+
+        fn write(&mut self, bytes: &[u8]) -> Result<(), T::Error> {
+            spi::Write::write(self, bytes)
+        }
+    }
+
+    impl<T: OutputPin> super::OutputPin for T {
+        type Error = T::Error;
+
+        fn set_high(&mut self) -> Result<(), T::Error> {
+            OutputPin::set_high(self)
+        }
+
+        fn set_low(&mut self) -> Result<(), T::Error> {
+            OutputPin::set_low(self)
+        }
+    }
+
+    impl<T: DelayUs<u32>> super::Delay for T {
+        fn delay_us(&mut self, us: u32) {
+            DelayUs::delay_us(self, us)
+        }
+    }
+}
+
+#[cfg(feature = "embedded-hal-1")]
+mod eh1 {
+    use embedded_hal_1::delay::DelayNs;
+    use embedded_hal_1::digital::OutputPin;
+    use embedded_hal_1::i2c::I2c;
+    use embedded_hal_1::spi::SpiDevice;
+
+    impl<T: I2c> super::I2cBus for T {
+        type Error = T::Error;
+
+        fn write(&mut self, address: u8, bytes: &[u8]) -> Result<(), T::Error> {
+            I2c::write(self, address, bytes)
+        }
+    }
+
+    impl<T: SpiDevice> super::SpiBus for T {
+        type Error = T::Error;
+
+        fn write(&mut self, bytes: &[u8]) -> Result<(), T::Error> {
+            SpiDevice::write(self, bytes)
+        }
+    }
+
+    impl<T: OutputPin> super::OutputPin for T {
+        type Error = T::Error;
+
+        fn set_high(&mut self) -> Result<(), T::Error> {
+            OutputPin::set_high(self)
+        }
+
+        fn set_low(&mut self) -> Result<(), T::Error> {
+            OutputPin::set_low(self)
+        }
+    }
+
+    impl<T: DelayNs> super::Delay for T {
+        fn delay_us(&mut self, us: u32) {
+            DelayNs::delay_us(self, us)
+        }
+    }
+}
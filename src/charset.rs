@@ -0,0 +1,91 @@
+//! Mapping between Unicode and the ST7032 character generator ROM.
+//!
+//! Codes `0x00..=0x07` select the custom characters stored in CGRAM,
+//! `0x20..=0x7D` follow ASCII except for `0x5C` which is the Yen sign,
+//! `0xA1..=0xDF` hold half-width Katakana and the upper rows contain
+//! Greek letters, accented Latin letters and various symbols.
+
+/// Full block
+pub(crate) const FULL_BLOCK: u8 = 0xff;
+
+/// Encode character into ST7032 ROM code
+pub fn encode(c: char) -> Option<u8> {
+    let code = match c {
+        '\u{0}'..='\u{7}' => c as u8,
+        '\\' => return None,
+        ' '..='}' => c as u8,
+        '¥' => 0x5c,
+        '→' => 0x7e,
+        '←' => 0x7f,
+        '\u{ff61}'..='\u{ff9f}' => (c as u32 - 0xff61 + 0xa1) as u8,
+        '。' => 0xa1,
+        '「' => 0xa2,
+        '」' => 0xa3,
+        '、' => 0xa4,
+        '・' | '·' => 0xa5,
+        'ー' => 0xb0,
+        '°' => 0xdf,
+        'α' => 0xe0,
+        'ä' => 0xe1,
+        'β' => 0xe2,
+        'ε' => 0xe3,
+        '\u{b5}' | '\u{3bc}' => 0xe4,
+        'σ' => 0xe5,
+        'ρ' => 0xe6,
+        '√' => 0xe8,
+        '¢' => 0xec,
+        'ñ' => 0xee,
+        'ö' => 0xef,
+        'θ' => 0xf2,
+        '∞' => 0xf3,
+        '\u{3a9}' | '\u{2126}' => 0xf4,
+        'ü' => 0xf5,
+        'Σ' => 0xf6,
+        'π' => 0xf7,
+        '千' => 0xfa,
+        '万' => 0xfb,
+        '円' => 0xfc,
+        '÷' => 0xfd,
+        '█' => FULL_BLOCK,
+        _ => return None,
+    };
+    Some(code)
+}
+
+/// Decode ST7032 ROM code, custom characters map to `'\u{0}'..='\u{7}'`
+pub fn decode(code: u8) -> Option<char> {
+    let c = match code {
+        0x00..=0x0f => (code & 0x07) as char,
+        0x5c => '¥',
+        0x20..=0x7d => code as char,
+        0x7e => '→',
+        0x7f => '←',
+        0xa0 => ' ',
+        0xdf => '°',
+        0xa1..=0xde => char::from_u32(code as u32 - 0xa1 + 0xff61)?,
+        0xe0 => 'α',
+        0xe1 => 'ä',
+        0xe2 => 'β',
+        0xe3 => 'ε',
+        0xe4 => 'µ',
+        0xe5 => 'σ',
+        0xe6 => 'ρ',
+        0xe8 => '√',
+        0xec => '¢',
+        0xee => 'ñ',
+        0xef => 'ö',
+        0xf2 => 'θ',
+        0xf3 => '∞',
+        0xf4 => 'Ω',
+        0xf5 => 'ü',
+        0xf6 => 'Σ',
+        0xf7 => 'π',
+        0xfa => '千',
+        0xfb => '万',
+        0xfc => '円',
+        0xfd => '÷',
+        FULL_BLOCK => '█',
+        _ => return None,
+    };
+    Some(c)
+}
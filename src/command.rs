@@ -0,0 +1,231 @@
+//! ST7032 instruction encoding shared by the blocking and async drivers.
+
+use crate::{charset, Bias, Config, Direction, Error};
+use core::str::Chars;
+
+/// Control byte for a single command: Co = 1, RS = 0
+pub(crate) const CONTROL_COMMAND: u8 = 0b_10000000;
+/// Control byte for a data stream: Co = 0, RS = 1
+pub(crate) const CONTROL_DATA: u8 = 0b_01000000;
+/// Max number of data bytes sent in one transaction, one DDRAM line
+pub(crate) const DATA_CHUNK: usize = 40;
+/// Time one byte with acknowledge takes on a 400 kHz I²C bus
+pub(crate) const I2C_BYTE_NS: u32 = 22_500;
+
+/// Delay before retrying the init sequence when the controller
+/// didn't acknowledge the first instruction
+pub(crate) const INIT_RETRY_US: u32 = 20_000;
+
+pub(crate) const CLEAR_DISPLAY: u8 = 0b_00000001;
+pub(crate) const RETURN_HOME: u8 = 0b_00000010;
+
+/// ST7032i instruction set
+#[derive(Debug, PartialEq, Clone, Copy)]
+pub(crate) enum InstructionSet {
+    Normal,
+    Extented,
+}
+
+pub(crate) fn entry_mode(entry: Direction, scroll: bool) -> u8 {
+    let mut command = 0b_00000100;
+    if scroll {
+        command |= 0b_00000001;
+    }
+    if entry == Direction::LeftToRigh {
+        command |= 0b_00000010;
+    }
+    command
+}
+
+pub(crate) fn display_control(display: bool, cursor: bool, blink: bool) -> u8 {
+    let mut command = 0b_00001000;
+    if blink {
+        command |= 0b_00000001;
+    }
+    if cursor {
+        command |= 0b_00000010;
+    }
+    if display {
+        command |= 0b_00000100;
+    }
+    command
+}
+
+pub(crate) fn function_set(is: InstructionSet, lines: u8, dbl: bool) -> u8 {
+    let mut command = 0b_00110000;
+    if lines > 1 {
+        command |= 0b_00001000;
+    } else if dbl {
+        command |= 0b_00000100;
+    }
+    if is == InstructionSet::Extented {
+        command |= 0b_00000001;
+    }
+    command
+}
+
+/// Cursor or display shift, normal instruction set only
+pub(crate) fn shift(display: bool, dir: Direction) -> u8 {
+    let mut command = 0b_00010000;
+    if display {
+        command |= 0b_00001000;
+    }
+    if dir == Direction::LeftToRigh {
+        command |= 0b_00000100;
+    }
+    command
+}
+
+/// Set CGRAM address to the first row of custom character `slot`,
+/// normal instruction set only
+pub(crate) fn cgram_address(slot: u8) -> u8 {
+    0b_01000000 | ((slot & 0x7) << 3)
+}
+
+pub(crate) fn ddram_address(address: u8) -> u8 {
+    0b_10000000 | address
+}
+
+/// DDRAM address the address counter moves to from `address`.
+/// In 2-line mode each line holds 40 addresses and the counter wraps
+/// from one line to the other, in 1-line mode it wraps within 0x00..0x50.
+pub(crate) fn next_address(address: u8, lines: u8, dir: Direction) -> u8 {
+    let forward = dir == Direction::LeftToRigh;
+    if lines > 1 {
+        match (address, forward) {
+            (0x27, true) => 0x40,
+            (0x67, true) => 0x00,
+            (0x00, false) => 0x67,
+            (0x40, false) => 0x27,
+            (address, true) => address + 1,
+            (address, false) => address - 1,
+        }
+    } else if forward {
+        (address + 1) % 0x50
+    } else {
+        (address + 0x50 - 1) % 0x50
+    }
+}
+
+/// Extended instruction set only
+pub(crate) fn icon_address(address: u8) -> u8 {
+    0b_01000000 | (address & 0x0f)
+}
+
+/// Extended instruction set only
+pub(crate) fn osc_config(bias: Bias, freq: u8) -> u8 {
+    assert!(freq < 8);
+    let mut command = 0b_00010000 | freq;
+    if bias == Bias::OneFourth {
+        command |= 0b_00001000;
+    }
+    command
+}
+
+/// Low 4 bits of the contrast, extended instruction set only
+pub(crate) fn contrast_low(contrast: u8) -> u8 {
+    0b_01110000 | (contrast & 0x0f)
+}
+
+/// Booster and icon switches with high 2 bits of the contrast,
+/// extended instruction set only
+pub(crate) fn power_icon_contrast(booster: bool, icon: bool, contrast: u8) -> u8 {
+    assert!(contrast < 64);
+    let mut command = 0b_01010000 | (contrast >> 4);
+    if booster {
+        command |= 0b_00000100;
+    }
+    if icon {
+        command |= 0b_00001000;
+    }
+    command
+}
+
+/// Extended instruction set only
+pub(crate) fn follower_control(on: bool, ratio: u8) -> u8 {
+    assert!(ratio < 8);
+    let mut command = 0b_01100000 | ratio;
+    if on {
+        command |= 0b_00001000;
+    }
+    command
+}
+
+/// Contrast, booster and icon setup, extended instruction set only
+pub(crate) fn contrast_setup(config: &Config, icon: bool) -> [u8; 2] {
+    [
+        contrast_low(config.contrast),
+        power_icon_contrast(config.booster, icon, config.contrast),
+    ]
+}
+
+/// Power-on analog setup, extended instruction set only
+pub(crate) fn power_setup(config: &Config, icon: bool) -> [u8; 4] {
+    let [contrast, power] = contrast_setup(config, icon);
+    [
+        osc_config(config.bias, config.osc_frequency),
+        contrast,
+        power,
+        follower_control(config.follower, config.follower_ratio),
+    ]
+}
+
+/// Power-on instruction sequence, each instruction paired with the delay
+/// in µs to wait after its execution time. Ends with the `entry` mode and
+/// `display` control instructions, the display must be cleared afterwards.
+pub(crate) fn init_sequence(
+    config: &Config,
+    lines: u8,
+    icon: bool,
+    entry: u8,
+    display: u8,
+) -> [(u8, u32); 12] {
+    let dbl = config.double_height;
+    let [osc, contrast, power, follower] = power_setup(config, icon);
+    [
+        (function_set(InstructionSet::Normal, 1, false), 1_000),
+        (function_set(InstructionSet::Extented, 1, false), 5_000),
+        (function_set(InstructionSet::Extented, 1, false), 5_000),
+        (function_set(InstructionSet::Extented, lines, dbl), 5_000),
+        (display & !0b_00000100, 0),
+        (osc, 0),
+        (contrast, 0),
+        (power, 0),
+        (follower, 0),
+        (function_set(InstructionSet::Normal, lines, dbl), 0),
+        (entry, 20_000),
+        (display, 0),
+    ]
+}
+
+/// Character code of `c`, `fallback` if the character ROM lacks it
+pub(crate) fn encode<E>(c: char, fallback: Option<u8>) -> Result<u8, Error<E>> {
+    charset::encode(c)
+        .or(fallback)
+        .ok_or(Error::UnsupportedChar)
+}
+
+/// Encode `chars` into `buf` until it is full or the string ends,
+/// returns the number of codes written, zero once `chars` is exhausted
+pub(crate) fn encode_chunk<E>(
+    chars: &mut Chars,
+    fallback: Option<u8>,
+    buf: &mut [u8; DATA_CHUNK],
+) -> Result<usize, Error<E>> {
+    let mut len = 0;
+    while len < DATA_CHUNK {
+        match chars.next() {
+            Some(c) => buf[len] = encode(c, fallback)?,
+            None => break,
+        }
+        len += 1;
+    }
+    Ok(len)
+}
+
+/// Prefix up to `DATA_CHUNK` data bytes with the data control byte
+pub(crate) fn data_frame<'a>(buf: &'a mut [u8; DATA_CHUNK + 1], data: &[u8]) -> &'a [u8] {
+    buf[0] = CONTROL_DATA;
+    buf[1..=data.len()].copy_from_slice(data);
+    &buf[..=data.len()]
+}
@@ -0,0 +1,214 @@
+/// LCD bias selection
+#[derive(Debug, PartialEq, Clone, Copy)]
+pub enum Bias {
+    /// 1/5 bias
+    OneFifth,
+    /// 1/4 bias
+    OneFourth,
+}
+
+/// Max number of columns, reached in 1-line mode.
+/// No display has more character cells in total.
+pub(crate) const MAX_COLS: usize = 80;
+/// Max number of rows
+pub(crate) const MAX_ROWS: usize = 2;
+
+/// Display geometry in characters
+///
+/// The ST7032 drives one or two lines. In 1-line mode DDRAM is contiguous
+/// and up to 80 columns can be addressed, in 2-line mode each row holds up
+/// to 40 columns. Modules wired as 16x1 with the right half starting at
+/// DDRAM address 0x40 should be described as 8x2.
+#[derive(Debug, PartialEq, Clone, Copy)]
+pub struct DisplaySize {
+    pub(crate) cols: u8,
+    pub(crate) rows: u8,
+}
+
+impl DisplaySize {
+    /// Create display size of `cols` x `rows` characters,
+    /// `None` if the controller can't drive such a display
+    pub const fn new(cols: u8, rows: u8) -> Option<Self> {
+        let valid = match rows {
+            1 => cols > 0 && cols as usize <= MAX_COLS,
+            2 => cols > 0 && cols as usize <= MAX_COLS / MAX_ROWS,
+            _ => false,
+        };
+        if valid {
+            Some(DisplaySize { cols, rows })
+        } else {
+            None
+        }
+    }
+
+    /// Number of columns
+    pub fn cols(&self) -> u8 {
+        self.cols
+    }
+
+    /// Number of rows
+    pub fn rows(&self) -> u8 {
+        self.rows
+    }
+
+    /// DDRAM address of the specified location
+    pub(crate) fn address(&self, row: u8, col: u8) -> Option<u8> {
+        if row >= 2 || row >= self.rows || col >= self.cols {
+            return None;
+        }
+        Some(row * 0x40 + col)
+    }
+}
+
+/// Instruction execution delays
+#[derive(Debug, PartialEq, Clone, Copy)]
+pub enum Timing {
+    /// 1 ms per instruction and 2 ms for clear and return home
+    Conservative,
+    /// Execution times from the datasheet, scaled by the oscillator frequency
+    Datasheet,
+}
+
+/// Execution time of a data write in ns at 183Hz frame frequency
+const DATA_WRITE_NS: u32 = 26_300;
+
+/// Frame frequency in Hz for each internal oscillator setting
+const FRAME_FREQUENCY: [u32; 8] = [122, 131, 144, 161, 183, 221, 274, 347];
+
+/// Power and analog settings applied by `init`
+#[derive(Debug, PartialEq, Clone, Copy)]
+pub struct Config {
+    pub(crate) bias: Bias,
+    pub(crate) osc_frequency: u8,
+    pub(crate) booster: bool,
+    pub(crate) follower: bool,
+    pub(crate) follower_ratio: u8,
+    pub(crate) contrast: u8,
+    pub(crate) double_height: bool,
+    pub(crate) timing: Timing,
+}
+
+impl Default for Config {
+    fn default() -> Self {
+        Config {
+            bias: Bias::OneFourth,
+            osc_frequency: 0,
+            booster: true,
+            follower: true,
+            follower_ratio: 0,
+            contrast: 0,
+            double_height: false,
+            timing: Timing::Conservative,
+        }
+    }
+}
+
+impl Config {
+    /// Typical settings for 3.3V COG modules, internal booster enabled
+    pub fn cog_3v3() -> Self {
+        Config {
+            bias: Bias::OneFifth,
+            osc_frequency: 4,
+            booster: true,
+            follower: true,
+            follower_ratio: 4,
+            contrast: 32,
+            double_height: false,
+            timing: Timing::Conservative,
+        }
+    }
+
+    /// Typical settings for 5V COG modules, internal booster disabled
+    pub fn cog_5v() -> Self {
+        Config {
+            booster: false,
+            contrast: 35,
+            ..Config::cog_3v3()
+        }
+    }
+
+    /// Set LCD bias
+    pub fn bias(mut self, bias: Bias) -> Self {
+        self.bias = bias;
+        self
+    }
+
+    /// Set internal oscillator frequency (0..=7), larger values are clamped
+    pub fn osc_frequency(mut self, freq: u8) -> Self {
+        self.osc_frequency = freq.min(7);
+        self
+    }
+
+    /// Enable or disable internal voltage booster
+    pub fn booster(mut self, on: bool) -> Self {
+        self.booster = on;
+        self
+    }
+
+    /// Enable or disable voltage follower and set its amplified ratio (0..=7),
+    /// larger ratios are clamped
+    pub fn follower(mut self, on: bool, ratio: u8) -> Self {
+        self.follower = on;
+        self.follower_ratio = ratio.min(7);
+        self
+    }
+
+    /// Set initial contrast (0..=63), larger values are clamped
+    pub fn contrast(mut self, contrast: u8) -> Self {
+        self.contrast = contrast.min(63);
+        self
+    }
+
+    /// Use double height (5x16) font, the display runs in 1-line mode
+    pub fn double_height(mut self, on: bool) -> Self {
+        self.double_height = on;
+        self
+    }
+
+    /// Set instruction execution delays
+    pub fn timing(mut self, timing: Timing) -> Self {
+        self.timing = timing;
+        self
+    }
+
+    /// Number of lines driven, double height font uses 1-line mode
+    pub(crate) fn lines(&self, size: DisplaySize) -> u8 {
+        if self.double_height {
+            1
+        } else {
+            size.rows
+        }
+    }
+
+    /// Execution time of most instructions and data writes
+    pub(crate) fn command_delay_us(&self) -> u32 {
+        match self.timing {
+            Timing::Conservative => 1_000,
+            Timing::Datasheet => self.scale_exec_time(DATA_WRITE_NS),
+        }
+    }
+
+    /// Execution time of Clear Display and Return Home
+    pub(crate) fn clear_delay_us(&self) -> u32 {
+        match self.timing {
+            Timing::Conservative => 2_000,
+            Timing::Datasheet => self.scale_exec_time(1_080_000),
+        }
+    }
+
+    /// Whether the controller executes a data write within `byte_ns`,
+    /// the time one streamed byte takes on the bus, whatever the timing
+    pub(crate) fn keeps_up_with(&self, byte_ns: u32) -> bool {
+        self.scale_ns(DATA_WRITE_NS) <= byte_ns
+    }
+
+    fn scale_exec_time(&self, ns: u32) -> u32 {
+        self.scale_ns(ns).div_ceil(1_000)
+    }
+
+    /// Datasheet times are given for fOSC = 380kHz (183Hz frame frequency)
+    fn scale_ns(&self, ns: u32) -> u32 {
+        let freq = FRAME_FREQUENCY[self.osc_frequency as usize & 0x7];
+        (ns as u64 * 183 / freq as u64) as u32
+    }
+}
@@ -0,0 +1,76 @@
+use crate::bus::Delay;
+use crate::interface::Interface;
+use crate::{Error, ST7032i};
+
+/// Number of CGRAM slots
+const SLOTS: usize = 8;
+
+/// Custom character bitmap, 5 bits of each of the 8 rows are used
+pub type Glyph = [u8; 8];
+
+/// Custom character manager, allocates CGRAM slots on demand.
+///
+/// Glyphs are identified by their bitmap. Loading a glyph that is already
+/// in CGRAM only returns its char code, otherwise a free slot is used or the
+/// least recently used glyph is evicted. Evicted glyphs still shown on
+/// screen change to the new bitmap, so a single screen should not use more
+/// than 8 glyphs at once.
+#[derive(Debug, Default)]
+pub struct GlyphManager {
+    slots: [Option<Glyph>; SLOTS],
+    used: [u32; SLOTS],
+    clock: u32,
+}
+
+impl GlyphManager {
+    /// Create manager with all slots free
+    pub fn new() -> Self {
+        GlyphManager::default()
+    }
+
+    /// Make sure `glyph` is in CGRAM and return its char code
+    pub fn load<DI, E, D>(
+        &mut self,
+        display: &mut ST7032i<DI, D>,
+        glyph: &Glyph,
+    ) -> Result<u8, Error<E>>
+    where
+        DI: Interface<Error = E>,
+        D: Delay,
+    {
+        if let Some(slot) = self.find(glyph) {
+            self.touch(slot);
+            return Ok(slot as u8);
+        }
+        let slot = match self.slots.iter().position(Option::is_none) {
+            Some(slot) => slot,
+            None => (0..SLOTS).min_by_key(|&slot| self.used[slot]).unwrap_or(0),
+        };
+        self.slots[slot] = None;
+        display.create_char(slot as u8, *glyph)?;
+        self.slots[slot] = Some(*glyph);
+        self.touch(slot);
+        Ok(slot as u8)
+    }
+
+    /// Char code of `glyph` if it is loaded, without marking it as used
+    pub fn code(&self, glyph: &Glyph) -> Option<u8> {
+        self.find(glyph).map(|slot| slot as u8)
+    }
+
+    /// Forget all glyphs, e.g. after `init` or a direct `create_char`
+    pub fn reset(&mut self) {
+        *self = GlyphManager::default();
+    }
+
+    fn find(&self, glyph: &Glyph) -> Option<usize> {
+        self.slots
+            .iter()
+            .position(|slot| slot.as_ref() == Some(glyph))
+    }
+
+    fn touch(&mut self, slot: usize) {
+        self.clock = self.clock.wrapping_add(1);
+        self.used[slot] = self.clock;
+    }
+}
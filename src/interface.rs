@@ -0,0 +1,246 @@
+//! Transports connecting the driver to ST7032 family controllers.
+//!
+//! The instruction set is the same for every variant, only the way
+//! instructions and display data reach the controller differs.
+
+use crate::bus::{Delay, I2cBus, OutputPin, SpiBus};
+use crate::command::{self, CONTROL_COMMAND, DATA_CHUNK, I2C_BYTE_NS};
+
+/// Controller interface
+pub trait Interface {
+    type Error;
+
+    /// Shortest time one data byte takes on the bus when several bytes are
+    /// sent in one transfer, `None` if the transport sends one at a time.
+    /// The driver streams data only when the controller writes a byte
+    /// within that time, otherwise it waits for every byte to be executed.
+    const STREAM_BYTE_NS: Option<u32> = None;
+
+    /// Prepare the interface after power on, called first by `init`
+    fn init(&mut self) -> Result<(), Self::Error> {
+        Ok(())
+    }
+
+    /// Send single instruction (RS = 0)
+    fn send_command(&mut self, command: u8) -> Result<(), Self::Error>;
+
+    /// Send display data (RS = 1)
+    fn send_data(&mut self, data: &[u8]) -> Result<(), Self::Error>;
+}
+
+/// I²C interface of the ST7032i
+///
+/// One byte takes 9 clock cycles on the bus, 22.5 µs at 400 kHz, while the
+/// controller writes it in 26.3 µs at 183 Hz frame frequency, scaled by the
+/// oscillator setting. Display data is streamed with `osc_frequency(5)` and
+/// above (≈21.8 µs), lower settings write one byte per transfer.
+#[derive(Debug)]
+pub struct I2cInterface<I2C> {
+    i2c: I2C,
+    address: u8,
+}
+
+impl<I2C: I2cBus> I2cInterface<I2C> {
+    /// Create interface for the controller at `address`
+    pub fn new(i2c: I2C, address: u8) -> Self {
+        I2cInterface { i2c, address }
+    }
+
+    /// Destroy interface, return I²C bus
+    pub fn release(self) -> I2C {
+        self.i2c
+    }
+
+    pub(crate) fn set_address(&mut self, address: u8) {
+        self.address = address;
+    }
+}
+
+impl<I2C: I2cBus> Interface for I2cInterface<I2C> {
+    type Error = I2C::Error;
+
+    const STREAM_BYTE_NS: Option<u32> = Some(I2C_BYTE_NS);
+
+    fn send_command(&mut self, command: u8) -> Result<(), Self::Error> {
+        self.i2c.write(self.address, &[CONTROL_COMMAND, command])
+    }
+
+    fn send_data(&mut self, data: &[u8]) -> Result<(), Self::Error> {
+        let mut buf = [0; DATA_CHUNK + 1];
+        for chunk in data.chunks(DATA_CHUNK) {
+            self.i2c
+                .write(self.address, command::data_frame(&mut buf, chunk))?;
+        }
+        Ok(())
+    }
+}
+
+/// SPI interface error
+#[derive(Debug, PartialEq)]
+pub enum SpiError<S, P> {
+    /// SPI bus error
+    Spi(S),
+    /// RS pin error
+    Pin(P),
+}
+
+/// 4-wire SPI interface of the ST7032, RS is driven by a GPIO.
+///
+/// With embedded-hal 1.0 chip select is handled by the `SpiDevice`,
+/// with embedded-hal 0.2 CSB has to be driven by the caller or tied low.
+#[derive(Debug)]
+pub struct SpiInterface<SPI, RS> {
+    spi: SPI,
+    rs: RS,
+}
+
+impl<SPI: SpiBus, RS: OutputPin> SpiInterface<SPI, RS> {
+    /// Create interface, RS pin selects instruction (low) or data (high)
+    pub fn new(spi: SPI, rs: RS) -> Self {
+        SpiInterface { spi, rs }
+    }
+
+    /// Destroy interface, return SPI bus and RS pin
+    pub fn release(self) -> (SPI, RS) {
+        (self.spi, self.rs)
+    }
+}
+
+impl<SPI: SpiBus, RS: OutputPin> Interface for SpiInterface<SPI, RS> {
+    type Error = SpiError<SPI::Error, RS::Error>;
+
+    fn send_command(&mut self, command: u8) -> Result<(), Self::Error> {
+        self.rs.set_low().map_err(SpiError::Pin)?;
+        self.spi.write(&[command]).map_err(SpiError::Spi)
+    }
+
+    fn send_data(&mut self, data: &[u8]) -> Result<(), Self::Error> {
+        self.rs.set_high().map_err(SpiError::Pin)?;
+        self.spi.write(data).map_err(SpiError::Spi)
+    }
+}
+
+/// 4-bit or 8-bit parallel interface of the ST7032, write only (R/W tied low).
+///
+/// Enable strobe timing needs a delay provider of its own.
+#[derive(Debug)]
+pub struct ParallelInterface<RS, EN, P, D, const N: usize> {
+    rs: RS,
+    en: EN,
+    data: [P; N],
+    delay: D,
+}
+
+impl<RS, EN, P, D, E> ParallelInterface<RS, EN, P, D, 4>
+where
+    RS: OutputPin<Error = E>,
+    EN: OutputPin<Error = E>,
+    P: OutputPin<Error = E>,
+    D: Delay,
+{
+    /// Create 4-bit interface, `data` are DB4..DB7 pins
+    pub fn new_4bit(rs: RS, en: EN, data: [P; 4], delay: D) -> Self {
+        ParallelInterface {
+            rs,
+            en,
+            data,
+            delay,
+        }
+    }
+}
+
+impl<RS, EN, P, D, E> ParallelInterface<RS, EN, P, D, 8>
+where
+    RS: OutputPin<Error = E>,
+    EN: OutputPin<Error = E>,
+    P: OutputPin<Error = E>,
+    D: Delay,
+{
+    /// Create 8-bit interface, `data` are DB0..DB7 pins
+    pub fn new_8bit(rs: RS, en: EN, data: [P; 8], delay: D) -> Self {
+        ParallelInterface {
+            rs,
+            en,
+            data,
+            delay,
+        }
+    }
+}
+
+impl<RS, EN, P, D, E, const N: usize> ParallelInterface<RS, EN, P, D, N>
+where
+    RS: OutputPin<Error = E>,
+    EN: OutputPin<Error = E>,
+    P: OutputPin<Error = E>,
+    D: Delay,
+{
+    /// Destroy interface, return pins and delay instance
+    pub fn release(self) -> (RS, EN, [P; N], D) {
+        (self.rs, self.en, self.data, self.delay)
+    }
+
+    fn write_byte(&mut self, byte: u8) -> Result<(), E> {
+        if N == 8 {
+            self.write_bits(byte)
+        } else {
+            self.write_bits(byte >> 4)?;
+            self.write_bits(byte & 0x0f)
+        }
+    }
+
+    fn write_bits(&mut self, bits: u8) -> Result<(), E> {
+        for (idx, pin) in self.data.iter_mut().enumerate() {
+            if bits & (1 << idx) != 0 {
+                pin.set_high()?;
+            } else {
+                pin.set_low()?;
+            }
+        }
+        self.en.set_high()?;
+        self.delay.delay_us(1);
+        self.en.set_low()?;
+        self.delay.delay_us(1);
+        Ok(())
+    }
+}
+
+impl<RS, EN, P, D, E, const N: usize> Interface for ParallelInterface<RS, EN, P, D, N>
+where
+    RS: OutputPin<Error = E>,
+    EN: OutputPin<Error = E>,
+    P: OutputPin<Error = E>,
+    D: Delay,
+{
+    type Error = E;
+
+    fn init(&mut self) -> Result<(), E> {
+        self.rs.set_low()?;
+        self.en.set_low()?;
+        if N == 4 {
+            // The controller wakes up in 8-bit mode, switch to 4-bit
+            // with single nibble writes of Function Set.
+            for &(nibble, wait) in [(0x3, 4_100), (0x3, 100), (0x3, 100), (0x2, 100)].iter() {
+                self.write_bits(nibble)?;
+                self.delay.delay_us(wait);
+            }
+        }
+        Ok(())
+    }
+
+    fn send_command(&mut self, mut command: u8) -> Result<(), E> {
+        if N == 4 && command & 0b_11100000 == 0b_00100000 {
+            // Function Set: clear DL to stay in 4-bit mode
+            command &= !0b_00010000;
+        }
+        self.rs.set_low()?;
+        self.write_byte(command)
+    }
+
+    fn send_data(&mut self, data: &[u8]) -> Result<(), E> {
+        self.rs.set_high()?;
+        for byte in data {
+            self.write_byte(*byte)?;
+        }
+        Ok(())
+    }
+}
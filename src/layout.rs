@@ -0,0 +1,122 @@
+use crate::bus::Delay;
+use crate::config::{MAX_COLS, MAX_ROWS};
+use crate::interface::Interface;
+use crate::{Error, ST7032i};
+
+/// Marker replacing the end of truncated text
+const ELLIPSIS: &[u8] = b"...";
+
+/// Horizontal text alignment
+#[derive(Debug, PartialEq, Clone, Copy)]
+pub enum Align {
+    Left,
+    Center,
+    Right,
+}
+
+impl<DI, E, D> ST7032i<DI, D>
+where
+    DI: Interface<Error = E>,
+    D: Delay,
+{
+    /// Write string at specified location, clipped at the end of the row
+    pub fn write_at(&mut self, row: u8, col: u8, s: &str) -> Result<(), Error<E>> {
+        let width = self.space(row, col)?;
+        let mut cells = [b' '; MAX_COLS];
+        let mut len = 0;
+        for c in s.chars().take(width) {
+            cells[len] = self.encode(c)?;
+            len += 1;
+        }
+        self.move_cursor(row, col)?;
+        self.write_bytes(&cells[..len])
+    }
+
+    /// Write string aligned in a field of `width` cells padded with spaces.
+    /// Text longer than the field is truncated with an ellipsis.
+    pub fn write_aligned(
+        &mut self,
+        row: u8,
+        col: u8,
+        width: u8,
+        s: &str,
+        align: Align,
+    ) -> Result<(), Error<E>> {
+        let width = width as usize;
+        if width > self.space(row, col)? {
+            return Err(Error::OutOfRange);
+        }
+        let mut text = [b' '; MAX_COLS];
+        let mut len = 0;
+        for c in s.chars() {
+            if len == width {
+                truncate(&mut text[..width]);
+                break;
+            }
+            text[len] = self.encode(c)?;
+            len += 1;
+        }
+        let offset = match align {
+            Align::Left => 0,
+            Align::Center => (width - len) / 2,
+            Align::Right => width - len,
+        };
+        let mut cells = [b' '; MAX_COLS];
+        cells[offset..offset + len].copy_from_slice(&text[..len]);
+        self.move_cursor(row, col)?;
+        self.write_bytes(&cells[..width])
+    }
+
+    /// Word wrap string over the rows from `row` to the bottom of the
+    /// display, padding every row with spaces. Words longer than a row are
+    /// broken, text that does not fit is truncated with an ellipsis.
+    pub fn write_wrapped(&mut self, row: u8, s: &str) -> Result<(), Error<E>> {
+        let cols = self.space(row, 0)?;
+        let rows = (self.lines() - row) as usize;
+        let mut lines = [[b' '; MAX_COLS]; MAX_ROWS];
+        let (mut line, mut pos) = (0, 0);
+        'words: for word in s.split_whitespace() {
+            if pos > 0 {
+                if pos + 1 + word.chars().count() > cols {
+                    line += 1;
+                    pos = 0;
+                } else {
+                    pos += 1;
+                }
+            }
+            for c in word.chars() {
+                if pos == cols {
+                    line += 1;
+                    pos = 0;
+                }
+                if line == rows {
+                    truncate(&mut lines[rows - 1][..cols]);
+                    break 'words;
+                }
+                lines[line][pos] = self.encode(c)?;
+                pos += 1;
+            }
+        }
+        for (idx, cells) in lines[..rows].iter().enumerate() {
+            self.move_cursor(row + idx as u8, 0)?;
+            self.write_bytes(&cells[..cols])?;
+        }
+        Ok(())
+    }
+
+    /// Number of cells from specified location to the end of the row
+    fn space(&self, row: u8, col: u8) -> Result<usize, Error<E>> {
+        if row >= self.lines() || col >= self.size.cols {
+            return Err(Error::OutOfRange);
+        }
+        Ok((self.size.cols - col) as usize)
+    }
+}
+
+/// Replace the end of `cells` with an ellipsis if it fits
+fn truncate(cells: &mut [u8]) {
+    if cells.len() > ELLIPSIS.len() {
+        let start = cells.len() - ELLIPSIS.len();
+        cells[start..].copy_from_slice(ELLIPSIS);
+    }
+}
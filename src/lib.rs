@@ -7,6 +7,17 @@
 //!
 //! - [Details and datasheet](http://www.newhavendisplay.com/app_notes/ST7032.pdf)
 //!
+//! ## Cargo features
+//!
+//! - `eh02` (default): use `embedded-hal` 0.2 traits
+//! - `embedded-hal-1`: use `embedded-hal` 1.0 traits, requires disabling
+//!   the default features as it can't be combined with `eh02`
+//! - `async`: `ST7032iAsync` driver based on `embedded-hal-async`
+//! - `std`: link the standard library
+//! - `mock`: simulated device in [`mock`] for host testing and a terminal
+//!   backend in [`terminal`] drawing it, bus recorder in [`trace`], implies
+//!   `std` and needs `eh02` or `embedded-hal-1`
+//!
 //! ## Usage
 //!
 //! ### Instantiating
@@ -23,33 +34,108 @@
 //! ```no_run
 //! use core::fmt::Write;
 //! use linux_embedded_hal::{Delay, I2cdev};
-//! use st7032i::ST7032i;
+//! use st7032i::{DisplaySize, ST7032i};
 //!
-//! # fn main() {
+//! # #[cfg(feature = "eh02")]
+//! # fn main() -> Result<(), Box<dyn std::error::Error>> {
 //! let dev = I2cdev::new("/dev/i2c-1")?;
-//! let mut display = ST7032i::new(dev, Delay, 2);
-//! display.init()?;
+//! let mut display = ST7032i::new(dev, Delay, DisplaySize::new(16, 2).unwrap());
+//! display.init().unwrap();
+//! display.set_contrast(40).unwrap();
 //! write!(display, "Hello")?;
-//! display.move_cursor(1, 0)?;
+//! display.move_cursor(1, 0).unwrap();
 //! write!(display, "Rust")?;
+//! # Ok(())
 //! # }
+//! # #[cfg(not(feature = "eh02"))]
+//! # fn main() {}
 //! ```
+//!
+//! ### Sharing the bus
+//!
+//! The driver takes ownership of the bus, `release` gives it back together
+//! with the delay. To keep the display next to other I²C peripherals lend
+//! it `&mut I2C` wrapped in `bus::BorrowedI2c`, or wrap a `RefCell` holding
+//! the bus with `bus::SharedI2c`. With embedded-hal 1.0 the shared bus
+//! devices from `embedded-hal-bus` implement `I2c` and can be passed to
+//! `ST7032i::new` directly.
+//!
+//! ### Other interfaces
+//!
+//! SPI and parallel variants of the ST7032 share the instruction set.
+//! Create the driver with `ST7032i::with_interface` and one of
+//! `interface::SpiInterface` or `interface::ParallelInterface`.
 
 #![no_std]
 
-extern crate embedded_hal as hal;
+#[cfg(all(feature = "eh02", feature = "embedded-hal-1"))]
+compile_error!(
+    "features `eh02` and `embedded-hal-1` are mutually exclusive, \
+     disable the default features to use embedded-hal 1.0"
+);
 
+#[cfg(all(
+    feature = "mock",
+    not(any(feature = "eh02", feature = "embedded-hal-1"))
+))]
+compile_error!("feature `mock` needs `eh02` or `embedded-hal-1` to implement the bus traits");
+
+#[cfg(feature = "eh02")]
+extern crate embedded_hal as hal;
+#[cfg(feature = "std")]
+extern crate std;
+
+#[cfg(feature = "async")]
+mod asynch;
+mod bar;
+mod big;
+mod buffered;
+pub mod bus;
+pub mod charset;
+mod command;
+mod config;
+mod glyphs;
+pub mod interface;
+mod layout;
+mod marquee;
+#[cfg(feature = "mock")]
+pub mod mock;
+#[cfg(feature = "mock")]
+pub mod terminal;
+#[cfg(feature = "mock")]
+pub mod trace;
+
+#[cfg(feature = "async")]
+pub use asynch::ST7032iAsync;
+pub use bar::{HorizontalBar, VerticalBar};
+pub use buffered::BufferedDisplay;
+pub use config::{Bias, Config, DisplaySize, Timing};
+pub use glyphs::{Glyph, GlyphManager};
+pub use layout::Align;
+pub use marquee::{Marquee, MarqueeMode};
+
+use bus::{Delay, I2cBus};
+use command::{InstructionSet, DATA_CHUNK};
 use core::fmt;
-use hal::blocking::delay::DelayMs;
-use hal::blocking::i2c::{Read, Write, WriteRead};
+use interface::{I2cInterface, Interface};
 
+/// Default I²C address
 pub const I2C_ADRESS: u8 = 0x3e;
 
-/// ST7032i instruction set
+/// Size of the icon RAM
+pub const ICON_RAM_SIZE: usize = 16;
+
+/// Errors returned by the driver
 #[derive(Debug, PartialEq)]
-enum InstructionSet {
-    Normal,
-    Extented,
+pub enum Error<E> {
+    /// Bus error
+    Bus(E),
+    /// Argument is out of the accepted range
+    InvalidArgument,
+    /// Position is outside of the display
+    OutOfRange,
+    /// Character can't be displayed
+    UnsupportedChar,
 }
 
 /// Moving direction
@@ -61,262 +147,481 @@ pub enum Direction {
 
 /// Driver for the ST7032i
 #[derive(Debug)]
-pub struct ST7032i<I2C, D> {
-    i2c: I2C,
+pub struct ST7032i<DI, D> {
+    interface: DI,
     delay: D,
     entry: Direction,
-    lines: u8,
+    size: DisplaySize,
+    instruction_set: InstructionSet,
     scroll: bool,
     display: bool,
     cursor: bool,
     blink: bool,
+    config: Config,
+    icon: bool,
+    icons: [u8; ICON_RAM_SIZE],
+    fallback: Option<u8>,
+    /// DDRAM address counter
+    ac: u8,
+    /// Number of columns the display is shifted to the left
+    shift: u8,
 }
 
-impl<I2C, E, D> ST7032i<I2C, D>
+impl<I2C, E, D> ST7032i<I2cInterface<I2C>, D>
 where
-    I2C: Read<Error = E> + Write<Error = E> + WriteRead<Error = E>,
-    D: DelayMs<u8>,
+    I2C: I2cBus<Error = E>,
+    D: Delay,
 {
     /// Initialize the ST7032i driver.
-    pub fn new(i2c: I2C, delay: D, lines: u8) -> Self {
+    pub fn new(i2c: I2C, delay: D, size: DisplaySize) -> Self {
+        Self::with_config(i2c, delay, size, Config::default())
+    }
+
+    /// Initialize the ST7032i driver with custom power settings.
+    pub fn with_config(i2c: I2C, delay: D, size: DisplaySize, config: Config) -> Self {
+        let interface = I2cInterface::new(i2c, I2C_ADRESS);
+        Self::with_interface(interface, delay, size, config)
+    }
+
+    /// Use I²C address other than the default `I2C_ADRESS`
+    pub fn with_address(mut self, address: u8) -> Self {
+        self.interface.set_address(address);
+        self
+    }
+
+    /// Destroy driver instance, return I²C bus and delay instance
+    pub fn release(self) -> (I2C, D) {
+        (self.interface.release(), self.delay)
+    }
+}
+
+impl<DI, E, D> ST7032i<DI, D>
+where
+    DI: Interface<Error = E>,
+    D: Delay,
+{
+    /// Initialize the driver for a controller behind any `Interface`.
+    pub fn with_interface(interface: DI, delay: D, size: DisplaySize, config: Config) -> Self {
         ST7032i {
-            i2c,
+            interface,
             delay,
-            lines,
-            entry: Direction::RightToLeft,
+            size,
+            instruction_set: InstructionSet::Normal,
+            entry: Direction::LeftToRigh,
             scroll: false,
             display: false,
             cursor: false,
             blink: false,
+            config,
+            icon: false,
+            icons: [0; ICON_RAM_SIZE],
+            fallback: Some(b'?'),
+            ac: 0,
+            shift: 0,
         }
     }
 
-    /// Initialize the display.
-    pub fn init(&mut self) -> Result<(), E> {
-        match self.send_function(InstructionSet::Normal, 1, false) {
-            Ok(_) => self.delay.delay_ms(1),
-            Err(_) => self.delay.delay_ms(20),
-        };
-
-        self.send_function(InstructionSet::Extented, 1, false)?;
-        self.delay.delay_ms(5);
-
-        self.send_function(InstructionSet::Extented, 1, false)?;
-        self.delay.delay_ms(5);
-
-        self.send_function(InstructionSet::Extented, self.lines, false)?;
-        self.delay.delay_ms(5);
-
-        self.off()?;
-
-        self.send_osc_config(true, 0)?;
-        self.send_contrast(0)?;
-        self.send_booster_config(true, false, 0)?;
-        self.send_follower_config(true, 0)?;
+    /// Destroy driver instance, return interface and delay instance
+    pub fn release_interface(self) -> (DI, D) {
+        (self.interface, self.delay)
+    }
 
-        self.send_entry_mode()?;
-        self.delay.delay_ms(20);
+    /// Display geometry
+    pub fn size(&self) -> DisplaySize {
+        self.size
+    }
 
-        self.on()?;
+    /// Initialize the display.
+    pub fn init(&mut self) -> Result<(), Error<E>> {
+        self.interface.init().map_err(Error::Bus)?;
+        let entry = command::entry_mode(self.entry, self.scroll);
+        let display = command::display_control(true, self.cursor, self.blink);
+        let sequence =
+            command::init_sequence(&self.config, self.lines(), self.icon, entry, display);
+        for (n, (command, delay_us)) in sequence.iter().enumerate() {
+            match self.send_command(*command) {
+                Err(_) if n == 0 => {
+                    self.delay.delay_us(command::INIT_RETRY_US);
+                    continue;
+                }
+                result => result?,
+            }
+            if *delay_us > 0 {
+                self.delay.delay_us(*delay_us);
+            }
+        }
+        self.instruction_set = InstructionSet::Normal;
+        self.display = true;
 
         self.clear()
     }
 
     /// Switch display on
-    pub fn on(&mut self) -> Result<(), E> {
+    pub fn on(&mut self) -> Result<(), Error<E>> {
         self.display = true;
         self.send_display_mode()
     }
 
     /// Switch display off
-    pub fn off(&mut self) -> Result<(), E> {
+    pub fn off(&mut self) -> Result<(), Error<E>> {
         self.display = false;
         self.send_display_mode()
     }
 
     /// Clear all the display data by writing "20H" (space code)
     /// to all DDRAM address, and set DDRAM address to "00H" into AC (address counter).
-    pub fn clear(&mut self) -> Result<(), E> {
-        const CLEAR_DISPLAY: u8 = 0b_00000001;
-        self.send_command(CLEAR_DISPLAY)?;
-        self.delay.delay_ms(2);
+    ///
+    /// The controller switches to left to right entry on clear,
+    /// a different entry direction is restored afterwards.
+    pub fn clear(&mut self) -> Result<(), Error<E>> {
+        self.send_instruction(command::CLEAR_DISPLAY, self.config.clear_delay_us())?;
+        self.ac = 0;
+        self.shift = 0;
+        if self.entry != Direction::LeftToRigh {
+            self.send_entry_mode()?;
+        }
         Ok(())
     }
 
     /// Set DDRAM address to "0" and return cursor to its original position if shifted.
     /// The contents of DDRAM are not changed.
-    pub fn home(&mut self) -> Result<(), E> {
-        const RETURN_HOME: u8 = 0b_00000010;
-        self.send_command(RETURN_HOME)?;
-        self.delay.delay_ms(2);
+    pub fn home(&mut self) -> Result<(), Error<E>> {
+        self.send_instruction(command::RETURN_HOME, self.config.clear_delay_us())?;
+        self.ac = 0;
+        self.shift = 0;
         Ok(())
     }
 
     /// Move cursor to specified location
-    pub fn move_cursor(&mut self, row: u8, col: u8) -> Result<(), E> {
-        let command = match row {
-            0 => col | 0b_10000000,
-            _ => col | 0b_11000000,
-        };
-        self.send_command(command)
+    pub fn move_cursor(&mut self, row: u8, col: u8) -> Result<(), Error<E>> {
+        if row > 0 && self.config.double_height {
+            return Err(Error::OutOfRange);
+        }
+        let address = self.size.address(row, col).ok_or(Error::OutOfRange)?;
+        self.send_command(command::ddram_address(address))?;
+        self.ac = address;
+        Ok(())
+    }
+
+    /// Cursor location in DDRAM as `(row, col)`, tracked through writes and
+    /// cursor commands. After writing past the end of a row the column may be
+    /// outside the display. Shifting the display, also by autoscroll, moves
+    /// the visible window but not this location, see `visible_cursor`.
+    pub fn cursor_position(&self) -> (u8, u8) {
+        if self.lines() > 1 && self.ac >= 0x40 {
+            (1, self.ac - 0x40)
+        } else {
+            (0, self.ac)
+        }
+    }
+
+    /// Number of columns the display is shifted to the left by
+    /// `shift_display` and autoscroll, reset by `clear` and `home`
+    pub fn display_shift(&self) -> u8 {
+        self.shift
+    }
+
+    /// Cursor location on the screen taking the display shift into account,
+    /// `None` if the cursor is outside the visible window
+    pub fn visible_cursor(&self) -> Option<(u8, u8)> {
+        let (row, col) = self.cursor_position();
+        let len = self.line_len();
+        let col = (col + len - self.shift % len) % len;
+        if col < self.size.cols {
+            Some((row, col))
+        } else {
+            None
+        }
+    }
+
+    /// Set display contrast (0..=63)
+    pub fn set_contrast(&mut self, contrast: u8) -> Result<(), Error<E>> {
+        if contrast > 63 {
+            return Err(Error::InvalidArgument);
+        }
+        self.config.contrast = contrast;
+        self.update_power_config()
+    }
+
+    /// Switch double height (5x16) font on or off.
+    /// Double height font puts the display into 1-line mode,
+    /// only row 0 is addressable while it is active.
+    pub fn set_double_height(&mut self, on: bool) -> Result<(), Error<E>> {
+        self.config.double_height = on;
+        self.set_instruction_set(self.instruction_set)
     }
 
     /// Show cursor
-    pub fn show_cursor(&mut self, blink: bool) -> Result<(), E> {
+    pub fn show_cursor(&mut self, blink: bool) -> Result<(), Error<E>> {
         self.cursor = true;
         self.blink = blink;
         self.send_display_mode()
     }
 
     /// Hide cursor
-    pub fn hide_cursor(&mut self) -> Result<(), E> {
+    pub fn hide_cursor(&mut self) -> Result<(), Error<E>> {
         self.cursor = false;
         self.blink = false;
         self.send_display_mode()
     }
 
     /// Enable autoscroll
-    pub fn enable_scroll(&mut self, entry: Direction) -> Result<(), E> {
+    pub fn enable_scroll(&mut self, entry: Direction) -> Result<(), Error<E>> {
         self.scroll = true;
         self.entry = entry;
         self.send_entry_mode()
     }
 
     /// Disable autoscroll
-    pub fn disable_scroll(&mut self) -> Result<(), E> {
+    pub fn disable_scroll(&mut self) -> Result<(), Error<E>> {
         self.scroll = false;
         self.send_entry_mode()
     }
 
     /// Shift display to specified direction
-    pub fn shift_display(&mut self, dir: Direction) -> Result<(), E> {
-        let mut command = 0b_00011000;
-        if dir == Direction::LeftToRigh {
-            command |= 0b_00000100;
-        }
-        self.send_command(command)
+    pub fn shift_display(&mut self, dir: Direction) -> Result<(), Error<E>> {
+        self.send_command(command::shift(true, dir))?;
+        self.step_shift(dir == Direction::RightToLeft);
+        Ok(())
     }
 
     /// Shift cursor to specified direction
-    pub fn shift_cursor(&mut self, dir: Direction) -> Result<(), E> {
-        let mut command = 0b_00010000;
-        if dir == Direction::LeftToRigh {
-            command |= 0b_00000100;
-        }
-        self.send_command(command)
+    pub fn shift_cursor(&mut self, dir: Direction) -> Result<(), Error<E>> {
+        self.send_command(command::shift(false, dir))?;
+        self.ac = command::next_address(self.ac, self.lines(), dir);
+        Ok(())
     }
 
-    /// Create custom character in CGRAM
-    pub fn create_char(&mut self, offset: u8, bitmap: [u8; 8]) -> Result<(), E> {
-        self.send_command(0x40 | ((offset & 0x7) << 3))?;
+    /// Create custom character in CGRAM, the cursor position is restored
+    pub fn create_char(&mut self, offset: u8, bitmap: [u8; 8]) -> Result<(), Error<E>> {
+        self.send_command(command::cgram_address(offset))?;
+        self.send_data(&bitmap)?;
+        self.restore_address()
+    }
 
-        let mut command = [0x40, 0];
-        for byte in bitmap.iter() {
-            command[1] = *byte;
-            self.i2c.write(I2C_ADRESS, &command).ok();
-        }
+    /// Set character code used for glyphs missing in the character ROM.
+    /// With `None` writing such a glyph fails with `Error::UnsupportedChar`.
+    pub fn set_fallback(&mut self, code: Option<u8>) {
+        self.fallback = code;
+    }
 
-        self.delay.delay_ms(1);
-        Ok(())
+    /// Enable icon display
+    pub fn enable_icons(&mut self) -> Result<(), Error<E>> {
+        self.icon = true;
+        self.update_power_config()
     }
 
-    fn send_entry_mode(&mut self) -> Result<(), E> {
-        let mut command = 0b_00000100;
-        if self.scroll {
-            command |= 0b_00000001;
+    /// Disable icon display, icon RAM content is preserved
+    pub fn disable_icons(&mut self) -> Result<(), Error<E>> {
+        self.icon = false;
+        self.update_power_config()
+    }
+
+    /// Current icon RAM content
+    pub fn icons(&self) -> &[u8; ICON_RAM_SIZE] {
+        &self.icons
+    }
+
+    /// Set or clear a single icon segment, other segments keep their state.
+    /// Each icon RAM address (0..16) holds 5 segment bits (0..5).
+    /// The cursor position is restored afterwards.
+    pub fn set_icon(&mut self, address: u8, bit: u8, on: bool) -> Result<(), Error<E>> {
+        if address as usize >= ICON_RAM_SIZE || bit > 4 {
+            return Err(Error::InvalidArgument);
         }
-        if self.entry == Direction::LeftToRigh {
-            command |= 0b_00000010;
+        let segments = &mut self.icons[address as usize];
+        if on {
+            *segments |= 1 << bit;
+        } else {
+            *segments &= !(1 << bit);
         }
-        self.send_command(command)
+        let segments = *segments;
+        self.send_icon_address(address)?;
+        self.send_data(&[segments])?;
+        self.restore_address()
     }
 
-    fn send_display_mode(&mut self) -> Result<(), E> {
-        let mut command = 0b_00001000;
-        if self.blink {
-            command |= 0b_00000001;
+    /// Overwrite the whole icon RAM, only the low 5 bits of each byte are used.
+    /// The cursor position is restored afterwards.
+    pub fn write_icons(&mut self, icons: &[u8; ICON_RAM_SIZE]) -> Result<(), Error<E>> {
+        for (shadow, segments) in self.icons.iter_mut().zip(icons.iter()) {
+            *shadow = segments & 0x1f;
         }
-        if self.cursor {
-            command |= 0b_00000010;
+        let icons = self.icons;
+        self.send_icon_address(0)?;
+        self.send_data(&icons)?;
+        self.restore_address()
+    }
+
+    /// Write string at current cursor position
+    pub fn write_str(&mut self, s: &str) -> Result<(), Error<E>> {
+        let mut chars = s.chars();
+        let mut buf = [0; DATA_CHUNK];
+        loop {
+            match command::encode_chunk(&mut chars, self.fallback, &mut buf)? {
+                0 => return Ok(()),
+                len => self.write_bytes(&buf[..len])?,
+            }
         }
-        if self.display {
-            command |= 0b_00000100;
+    }
+
+    /// Write raw character codes at current cursor position
+    pub fn write_bytes(&mut self, bytes: &[u8]) -> Result<(), Error<E>> {
+        self.send_data(bytes)?;
+        for _ in bytes {
+            self.ac = command::next_address(self.ac, self.lines(), self.entry);
+            if self.scroll {
+                self.step_shift(self.entry == Direction::LeftToRigh);
+            }
         }
-        self.send_command(command)
+        Ok(())
     }
 
-    fn send_function(&mut self, is: InstructionSet, lines: u8, dbl: bool) -> Result<(), E> {
-        let mut command = 0b_00110000;
-        if lines > 1 {
-            command |= 0b_00001000;
-        } else if dbl {
-            command |= 0b_00000100;
+    /// Write digits, `-`, `:`, `.` and spaces two rows tall starting at
+    /// column `col`. Digits and minus are 3 columns wide, the rest 1 column.
+    ///
+    /// The segment glyphs used by `s` are loaded through `glyphs`, so they
+    /// share CGRAM with bars and other managed glyphs. Up to 8 of them
+    /// are needed, glyphs evicted for them change on screen.
+    pub fn write_big(
+        &mut self,
+        s: &str,
+        col: u8,
+        glyphs: &mut GlyphManager,
+    ) -> Result<(), Error<E>> {
+        if self.lines() < 2 {
+            return Err(Error::InvalidArgument);
         }
-        if is == InstructionSet::Extented {
-            command |= 0b_00000001;
+        let mut top = [b' '; DATA_CHUNK];
+        let mut bottom = [b' '; DATA_CHUNK];
+        let mut len = 0;
+        for c in s.chars() {
+            let (upper, lower) = big::cells(c).ok_or(Error::UnsupportedChar)?;
+            let end = len + upper.len();
+            if col as usize + end > self.size.cols as usize {
+                return Err(Error::OutOfRange);
+            }
+            top[len..end].copy_from_slice(upper);
+            bottom[len..end].copy_from_slice(lower);
+            len = end;
         }
-        self.send_command(command)
+        // Replace segment numbers by the codes of the loaded glyphs
+        for cell in top[..len].iter_mut().chain(bottom[..len].iter_mut()) {
+            if let Some(glyph) = big::GLYPHS.get(*cell as usize) {
+                *cell = glyphs.load(self, glyph)?;
+            }
+        }
+        self.move_cursor(0, col)?;
+        self.write_bytes(&top[..len])?;
+        self.move_cursor(1, col)?;
+        self.write_bytes(&bottom[..len])
     }
 
-    fn send_osc_config(&mut self, bias: bool, freq: u8) -> Result<(), E> {
-        assert!(freq < 8);
-        let mut command = 0b_00010000 | freq;
-        if bias {
-            command |= 0b_00001000;
-        }
-        self.send_command(command)
+    fn send_entry_mode(&mut self) -> Result<(), Error<E>> {
+        self.send_command(command::entry_mode(self.entry, self.scroll))
     }
 
-    fn send_contrast(&mut self, contrast: u8) -> Result<(), E> {
-        assert!(contrast < 16);
-        self.send_command(0b_01110000 | contrast)
+    fn send_display_mode(&mut self) -> Result<(), Error<E>> {
+        self.send_command(command::display_control(
+            self.display,
+            self.cursor,
+            self.blink,
+        ))
     }
 
-    fn send_booster_config(&mut self, on: bool, icon: bool, contrast_low: u8) -> Result<(), E> {
-        assert!(contrast_low < 4);
-        let mut command = 0b_01010000 | contrast_low;
-        if on {
-            command |= 0b_00000100;
-        }
-        if icon {
-            command |= 0b_00001000;
+    fn send_function(&mut self, is: InstructionSet, lines: u8, dbl: bool) -> Result<(), Error<E>> {
+        self.instruction_set = is;
+        self.send_command(command::function_set(is, lines, dbl))
+    }
+
+    fn update_power_config(&mut self) -> Result<(), Error<E>> {
+        self.set_instruction_set(InstructionSet::Extented)?;
+        self.send_contrast()?;
+        self.set_instruction_set(InstructionSet::Normal)
+    }
+
+    fn restore_address(&mut self) -> Result<(), Error<E>> {
+        self.send_command(command::ddram_address(self.ac))
+    }
+
+    fn send_icon_address(&mut self, address: u8) -> Result<(), Error<E>> {
+        self.set_instruction_set(InstructionSet::Extented)?;
+        self.send_command(command::icon_address(address))?;
+        self.set_instruction_set(InstructionSet::Normal)
+    }
+
+    fn encode(&self, c: char) -> Result<u8, Error<E>> {
+        command::encode(c, self.fallback)
+    }
+
+    /// Number of lines driven, double height font uses 1-line mode
+    fn lines(&self) -> u8 {
+        self.config.lines(self.size)
+    }
+
+    /// Number of DDRAM addresses in a line
+    fn line_len(&self) -> u8 {
+        if self.lines() > 1 {
+            40
+        } else {
+            80
         }
-        self.send_command(command)
     }
 
-    fn send_follower_config(&mut self, on: bool, ratio: u8) -> Result<(), E> {
-        assert!(ratio < 8);
-        let mut command = 0b_01100000 | ratio;
-        if on {
-            command |= 0b_00001000;
+    /// Track a display shift by one column to the left or right
+    fn step_shift(&mut self, left: bool) {
+        let len = self.line_len();
+        self.shift = if left {
+            (self.shift + 1) % len
+        } else {
+            (self.shift + len - 1) % len
+        };
+    }
+
+    fn set_instruction_set(&mut self, is: InstructionSet) -> Result<(), Error<E>> {
+        let (lines, dbl) = (self.lines(), self.config.double_height);
+        self.send_function(is, lines, dbl)
+    }
+
+    fn send_contrast(&mut self) -> Result<(), Error<E>> {
+        for command in command::contrast_setup(&self.config, self.icon).iter() {
+            self.send_command(*command)?;
         }
-        self.send_command(command)
+        Ok(())
+    }
+
+    fn send_command(&mut self, command: u8) -> Result<(), Error<E>> {
+        self.send_instruction(command, self.config.command_delay_us())
     }
 
-    fn send_command(&mut self, command: u8) -> Result<(), E> {
-        self.i2c.write(I2C_ADRESS, &[0x80, command])?;
-        self.delay.delay_ms(1);
+    fn send_instruction(&mut self, command: u8, exec_us: u32) -> Result<(), Error<E>> {
+        self.interface.send_command(command).map_err(Error::Bus)?;
+        self.delay.delay_us(exec_us);
         Ok(())
     }
-}
 
-impl<I2C, E, D> fmt::Write for ST7032i<I2C, D>
-where
-    I2C: Read<Error = E> + Write<Error = E> + WriteRead<Error = E>,
-    D: DelayMs<u8>,
-{
-    fn write_str(&mut self, s: &str) -> fmt::Result {
-        for c in s.chars() {
-            self.write_char(c)?;
+    fn send_data(&mut self, data: &[u8]) -> Result<(), Error<E>> {
+        let exec_us = self.config.command_delay_us();
+        let streaming = DI::STREAM_BYTE_NS.is_some_and(|ns| self.config.keeps_up_with(ns));
+        if streaming {
+            self.interface.send_data(data).map_err(Error::Bus)?;
+            self.delay.delay_us(exec_us);
+        } else {
+            for byte in data {
+                self.interface.send_data(&[*byte]).map_err(Error::Bus)?;
+                self.delay.delay_us(exec_us);
+            }
         }
-        self.delay.delay_ms(1);
         Ok(())
     }
+}
 
-    fn write_char(&mut self, c: char) -> fmt::Result {
-        let mut command = [0x40, 0, 0, 0];
-        c.encode_utf8(&mut command[1..]);
-        self.i2c.write(I2C_ADRESS, &command[..2]).ok();
-        Ok(())
+impl<DI, E, D> fmt::Write for ST7032i<DI, D>
+where
+    DI: Interface<Error = E>,
+    D: Delay,
+{
+    fn write_str(&mut self, s: &str) -> fmt::Result {
+        ST7032i::write_str(self, s).map_err(|_| fmt::Error)
     }
 }
@@ -0,0 +1,171 @@
+use crate::bus::Delay;
+use crate::config::MAX_COLS;
+use crate::interface::Interface;
+use crate::{Error, ST7032i};
+use core::iter;
+
+/// Marquee scrolling mode
+#[derive(Debug, PartialEq, Clone, Copy)]
+pub enum MarqueeMode {
+    /// Scroll to the left and start over after a gap
+    Loop,
+    /// Scroll to the end of the text and back
+    Bounce,
+}
+
+/// Non-blocking scroller for text longer than its field.
+///
+/// The marquee redraws a window of the text in one row, so the rest of the
+/// display stays in place. Call `tick` from the main loop at a steady rate,
+/// speed and pauses are counted in ticks. Text that fits the field is drawn
+/// once and never scrolls.
+#[derive(Debug, Clone)]
+pub struct Marquee<'a> {
+    text: &'a str,
+    len: usize,
+    row: u8,
+    col: u8,
+    width: u8,
+    mode: MarqueeMode,
+    speed: u16,
+    pause: u16,
+    gap: u8,
+    offset: usize,
+    backward: bool,
+    wait: u16,
+    drawn: bool,
+}
+
+impl<'a> Marquee<'a> {
+    /// Marquee `width` cells wide at specified location
+    pub fn new(text: &'a str, row: u8, col: u8, width: u8) -> Self {
+        Marquee {
+            text,
+            len: text.chars().count(),
+            row,
+            col,
+            width,
+            mode: MarqueeMode::Loop,
+            speed: 1,
+            pause: 0,
+            gap: 4,
+            offset: 0,
+            backward: false,
+            wait: 0,
+            drawn: false,
+        }
+    }
+
+    /// Set scrolling mode, `Loop` by default
+    pub fn mode(mut self, mode: MarqueeMode) -> Self {
+        self.mode = mode;
+        self
+    }
+
+    /// Set number of ticks per one step, 1 by default
+    pub fn speed(mut self, ticks: u16) -> Self {
+        self.speed = ticks.max(1);
+        self
+    }
+
+    /// Set number of extra ticks to wait at the ends of the text, 0 by default
+    pub fn pause(mut self, ticks: u16) -> Self {
+        self.pause = ticks;
+        self
+    }
+
+    /// Set number of blank cells between the end and the start of the text
+    /// in `Loop` mode, 4 by default
+    pub fn gap(mut self, gap: u8) -> Self {
+        self.gap = gap;
+        self
+    }
+
+    /// Replace text and restart from the beginning
+    pub fn set_text(&mut self, text: &'a str) {
+        self.text = text;
+        self.len = text.chars().count();
+        self.restart();
+    }
+
+    /// Scroll back to the beginning, the next `tick` redraws the field
+    pub fn restart(&mut self) {
+        self.offset = 0;
+        self.backward = false;
+        self.wait = 0;
+        self.drawn = false;
+    }
+
+    /// Advance the animation, redraws the field when the window moves
+    pub fn tick<DI, E, D>(&mut self, display: &mut ST7032i<DI, D>) -> Result<(), Error<E>>
+    where
+        DI: Interface<Error = E>,
+        D: Delay,
+    {
+        if !self.drawn {
+            self.draw(display)?;
+            self.drawn = true;
+            self.wait = (self.speed - 1).saturating_add(self.pause);
+            return Ok(());
+        }
+        if self.len <= self.width as usize {
+            return Ok(());
+        }
+        if self.wait > 0 {
+            self.wait -= 1;
+            return Ok(());
+        }
+        self.wait = self.speed - 1;
+        match self.mode {
+            MarqueeMode::Loop => {
+                self.offset = (self.offset + 1) % (self.len + self.gap as usize);
+                if self.offset == 0 {
+                    self.wait = self.wait.saturating_add(self.pause);
+                }
+            }
+            MarqueeMode::Bounce if self.backward => {
+                self.offset -= 1;
+                if self.offset == 0 {
+                    self.backward = false;
+                    self.wait = self.wait.saturating_add(self.pause);
+                }
+            }
+            MarqueeMode::Bounce => {
+                self.offset += 1;
+                if self.offset == self.len - self.width as usize {
+                    self.backward = true;
+                    self.wait = self.wait.saturating_add(self.pause);
+                }
+            }
+        }
+        self.draw(display)
+    }
+
+    fn draw<DI, E, D>(&self, display: &mut ST7032i<DI, D>) -> Result<(), Error<E>>
+    where
+        DI: Interface<Error = E>,
+        D: Delay,
+    {
+        let width = self.width as usize;
+        if self.col as usize + width > display.size().cols as usize {
+            return Err(Error::OutOfRange);
+        }
+        let mut cells = [b' '; MAX_COLS];
+        let gap = match self.mode {
+            MarqueeMode::Loop if self.len > width => self.gap as usize,
+            _ => 0,
+        };
+        let window = self
+            .text
+            .chars()
+            .chain(iter::repeat_n(' ', gap))
+            .cycle()
+            .skip(self.offset)
+            .take(width.min(self.len + gap));
+        for (cell, c) in cells.iter_mut().zip(window) {
+            *cell = display.encode(c)?;
+        }
+        display.move_cursor(self.row, self.col)?;
+        display.write_bytes(&cells[..width])
+    }
+}
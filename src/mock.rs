@@ -0,0 +1,553 @@
+//! Software model of the ST7032i for host testing.
+//!
+//! `Device` decodes the I²C stream produced by the driver and keeps the
+//! controller state: instruction set, DDRAM, CGRAM, icon RAM, address
+//! counter, entry mode, display switches and display shift.
+//!
+//! ```
+//! use core::fmt::Write;
+//! use st7032i::mock::{Device, MockDelay};
+//! use st7032i::{DisplaySize, ST7032i};
+//!
+//! let size = DisplaySize::new(8, 2).unwrap();
+//! let mut display = ST7032i::new(Device::new(size), MockDelay::default(), size);
+//! display.init().unwrap();
+//! write!(display, "Hello").unwrap();
+//! display.move_cursor(1, 2).unwrap();
+//! write!(display, "Rust").unwrap();
+//!
+//! let (device, _) = display.release();
+//! assert_eq!(device.render(), "Hello   \n  Rust  ");
+//! ```
+
+use crate::charset;
+use crate::{DisplaySize, ICON_RAM_SIZE};
+use std::string::String;
+
+/// Size of the DDRAM address space
+const DDRAM_SIZE: usize = 0x80;
+/// Size of the CGRAM, 8 characters by 8 rows
+const CGRAM_SIZE: usize = 64;
+
+/// Mock bus error
+#[derive(Debug, PartialEq, Clone, Copy)]
+pub enum MockError {
+    /// Transaction was addressed to another device
+    Nack,
+    /// Reading is not supported by the ST7032i
+    Unsupported,
+}
+
+/// RAM selected by the last address instruction
+#[derive(Debug, PartialEq, Clone, Copy)]
+pub enum Target {
+    Ddram,
+    Cgram,
+    Icon,
+}
+
+/// Simulated ST7032i controller
+#[derive(Debug, Clone)]
+pub struct Device {
+    address: u8,
+    size: DisplaySize,
+    ddram: [u8; DDRAM_SIZE],
+    cgram: [u8; CGRAM_SIZE],
+    icon_ram: [u8; ICON_RAM_SIZE],
+    target: Target,
+    ac: u8,
+    extended: bool,
+    two_lines: bool,
+    double_height: bool,
+    increment: bool,
+    autoscroll: bool,
+    display_on: bool,
+    cursor: bool,
+    blink: bool,
+    shift: u8,
+    bias: bool,
+    osc_frequency: u8,
+    contrast: u8,
+    booster: bool,
+    icons_enabled: bool,
+    follower: bool,
+    follower_ratio: u8,
+}
+
+impl Device {
+    /// Create device at the default address with power-on state
+    pub fn new(size: DisplaySize) -> Self {
+        Device {
+            address: crate::I2C_ADRESS,
+            size,
+            ddram: [b' '; DDRAM_SIZE],
+            cgram: [0; CGRAM_SIZE],
+            icon_ram: [0; ICON_RAM_SIZE],
+            target: Target::Ddram,
+            ac: 0,
+            extended: false,
+            two_lines: false,
+            double_height: false,
+            increment: true,
+            autoscroll: false,
+            display_on: false,
+            cursor: false,
+            blink: false,
+            shift: 0,
+            bias: false,
+            osc_frequency: 0,
+            contrast: 0,
+            booster: false,
+            icons_enabled: false,
+            follower: false,
+            follower_ratio: 0,
+        }
+    }
+
+    /// Respond at `address` instead of the default one
+    pub fn with_address(mut self, address: u8) -> Self {
+        self.address = address;
+        self
+    }
+
+    /// Process one I²C write transaction
+    pub fn write(&mut self, address: u8, bytes: &[u8]) -> Result<(), MockError> {
+        if address != self.address {
+            return Err(MockError::Nack);
+        }
+        let mut iter = bytes.iter();
+        while let Some(&control) = iter.next() {
+            let rs = control & 0b_01000000 != 0;
+            if control & 0b_10000000 != 0 {
+                if let Some(&byte) = iter.next() {
+                    self.execute(rs, byte);
+                }
+            } else {
+                for &byte in iter.by_ref() {
+                    self.execute(rs, byte);
+                }
+            }
+        }
+        Ok(())
+    }
+
+    /// Execute instruction (`rs == false`) or write data (`rs == true`)
+    pub fn execute(&mut self, rs: bool, byte: u8) {
+        if rs {
+            self.write_data(byte);
+        } else {
+            self.execute_instruction(byte);
+        }
+    }
+
+    /// Visible screen as text, one line per row
+    pub fn render(&self) -> String {
+        let mut text = String::new();
+        for row in 0..self.rows() {
+            if row > 0 {
+                text.push('\n');
+            }
+            text.extend(
+                self.row(row)
+                    .map(|code| charset::decode(code).unwrap_or('?')),
+            );
+        }
+        text
+    }
+
+    /// Character codes visible in `row`
+    pub fn row(&self, row: u8) -> impl Iterator<Item = u8> + '_ {
+        (0..self.size.cols).map(move |col| self.ddram[self.visible_address(row, col) as usize])
+    }
+
+    /// Number of visible rows in the current mode
+    pub fn rows(&self) -> u8 {
+        if self.two_lines {
+            self.size.rows.min(2)
+        } else {
+            1
+        }
+    }
+
+    /// DDRAM address shown at the visible location
+    pub fn visible_address(&self, row: u8, col: u8) -> u8 {
+        let (base, len) = self.line(row);
+        base + ((col as u16 + self.shift as u16) % len as u16) as u8
+    }
+
+    /// Visible location of the address counter when it points to DDRAM
+    pub fn visible_cursor(&self) -> Option<(u8, u8)> {
+        if self.target != Target::Ddram {
+            return None;
+        }
+        (0..self.rows())
+            .flat_map(|row| (0..self.size.cols).map(move |col| (row, col)))
+            .find(|&(row, col)| self.visible_address(row, col) == self.ac)
+    }
+
+    /// Display geometry
+    pub fn size(&self) -> DisplaySize {
+        self.size
+    }
+
+    /// Raw DDRAM content indexed by address
+    pub fn ddram(&self) -> &[u8; DDRAM_SIZE] {
+        &self.ddram
+    }
+
+    /// Raw CGRAM content
+    pub fn cgram(&self) -> &[u8; CGRAM_SIZE] {
+        &self.cgram
+    }
+
+    /// Bitmap of custom character in `slot`
+    pub fn glyph(&self, slot: u8) -> [u8; 8] {
+        let mut bitmap = [0; 8];
+        let start = (slot as usize & 0x7) * 8;
+        bitmap.copy_from_slice(&self.cgram[start..start + 8]);
+        bitmap
+    }
+
+    /// Icon RAM content
+    pub fn icon_ram(&self) -> &[u8; ICON_RAM_SIZE] {
+        &self.icon_ram
+    }
+
+    /// RAM selected by the address counter
+    pub fn target(&self) -> Target {
+        self.target
+    }
+
+    /// Address counter
+    pub fn address_counter(&self) -> u8 {
+        self.ac
+    }
+
+    /// Extended instruction set is selected
+    pub fn extended(&self) -> bool {
+        self.extended
+    }
+
+    /// 2-line mode is selected
+    pub fn two_lines(&self) -> bool {
+        self.two_lines
+    }
+
+    /// Double height font is selected, effective in 1-line mode only
+    pub fn double_height(&self) -> bool {
+        self.double_height
+    }
+
+    /// Address counter increments after data writes
+    pub fn increment(&self) -> bool {
+        self.increment
+    }
+
+    /// Display shifts after data writes
+    pub fn autoscroll(&self) -> bool {
+        self.autoscroll
+    }
+
+    pub fn display_on(&self) -> bool {
+        self.display_on
+    }
+
+    pub fn cursor(&self) -> bool {
+        self.cursor
+    }
+
+    pub fn blink(&self) -> bool {
+        self.blink
+    }
+
+    /// Number of positions the display is shifted to the left
+    pub fn shift(&self) -> u8 {
+        self.shift
+    }
+
+    /// 1/4 bias is selected
+    pub fn bias(&self) -> bool {
+        self.bias
+    }
+
+    pub fn osc_frequency(&self) -> u8 {
+        self.osc_frequency
+    }
+
+    pub fn contrast(&self) -> u8 {
+        self.contrast
+    }
+
+    pub fn booster(&self) -> bool {
+        self.booster
+    }
+
+    pub fn icons_enabled(&self) -> bool {
+        self.icons_enabled
+    }
+
+    pub fn follower(&self) -> bool {
+        self.follower
+    }
+
+    pub fn follower_ratio(&self) -> u8 {
+        self.follower_ratio
+    }
+
+    fn execute_instruction(&mut self, command: u8) {
+        match command {
+            0b_00000001 => {
+                self.ddram = [b' '; DDRAM_SIZE];
+                self.set_ddram_address(0);
+                self.shift = 0;
+                self.increment = true;
+            }
+            0b_00000010..=0b_00000011 => {
+                self.set_ddram_address(0);
+                self.shift = 0;
+            }
+            0b_00000100..=0b_00000111 => {
+                self.increment = command & 0b_10 != 0;
+                self.autoscroll = command & 0b_01 != 0;
+            }
+            0b_00001000..=0b_00001111 => {
+                self.display_on = command & 0b_100 != 0;
+                self.cursor = command & 0b_010 != 0;
+                self.blink = command & 0b_001 != 0;
+            }
+            0b_00010000..=0b_00011111 if self.extended => {
+                self.bias = command & 0b_1000 != 0;
+                self.osc_frequency = command & 0b_0111;
+            }
+            0b_00010000..=0b_00011111 => {
+                let right = command & 0b_0100 != 0;
+                if command & 0b_1000 != 0 {
+                    self.shift_display(right);
+                } else {
+                    self.step_address(right);
+                }
+            }
+            0b_00100000..=0b_00111111 => {
+                self.two_lines = command & 0b_1000 != 0;
+                self.double_height = command & 0b_0100 != 0;
+                self.extended = command & 0b_0001 != 0;
+            }
+            0b_01000000..=0b_01111111 if !self.extended => {
+                self.target = Target::Cgram;
+                self.ac = command & 0b_00111111;
+            }
+            0b_01000000..=0b_01001111 => {
+                self.target = Target::Icon;
+                self.ac = command & 0b_00001111;
+            }
+            0b_01010000..=0b_01011111 => {
+                self.icons_enabled = command & 0b_1000 != 0;
+                self.booster = command & 0b_0100 != 0;
+                self.contrast = (self.contrast & 0x0f) | ((command & 0b_0011) << 4);
+            }
+            0b_01100000..=0b_01101111 => {
+                self.follower = command & 0b_1000 != 0;
+                self.follower_ratio = command & 0b_0111;
+            }
+            0b_01110000..=0b_01111111 => {
+                self.contrast = (self.contrast & 0x30) | (command & 0x0f);
+            }
+            _ => self.set_ddram_address(command & 0b_01111111),
+        }
+    }
+
+    fn write_data(&mut self, byte: u8) {
+        match self.target {
+            Target::Ddram => {
+                self.ddram[self.ac as usize] = byte;
+                self.step_address(self.increment);
+                if self.autoscroll {
+                    self.shift_display(!self.increment);
+                }
+            }
+            Target::Cgram => {
+                self.cgram[self.ac as usize] = byte;
+                self.step_address(self.increment);
+            }
+            Target::Icon => {
+                self.icon_ram[self.ac as usize] = byte & 0x1f;
+                self.step_address(self.increment);
+            }
+        }
+    }
+
+    fn set_ddram_address(&mut self, address: u8) {
+        self.target = Target::Ddram;
+        self.ac = address;
+    }
+
+    /// DDRAM base address and length of the line holding `row`
+    fn line(&self, row: u8) -> (u8, u8) {
+        if self.two_lines {
+            (if row > 0 { 0x40 } else { 0x00 }, 40)
+        } else {
+            (0x00, 80)
+        }
+    }
+
+    fn step_address(&mut self, forward: bool) {
+        self.ac = match self.target {
+            Target::Cgram => wrap(self.ac, forward, CGRAM_SIZE as u8),
+            Target::Icon => wrap(self.ac, forward, ICON_RAM_SIZE as u8),
+            Target::Ddram if self.two_lines => match (self.ac, forward) {
+                (0x27, true) => 0x40,
+                (0x67, true) => 0x00,
+                (0x00, false) => 0x67,
+                (0x40, false) => 0x27,
+                (ac, true) => ac + 1,
+                (ac, false) => ac - 1,
+            },
+            Target::Ddram => wrap(self.ac, forward, 80),
+        };
+    }
+
+    fn shift_display(&mut self, right: bool) {
+        let len = self.line(0).1;
+        self.shift = wrap(self.shift, !right, len);
+    }
+}
+
+fn wrap(value: u8, forward: bool, len: u8) -> u8 {
+    if forward {
+        (value + 1) % len
+    } else {
+        (value + len - 1) % len
+    }
+}
+
+/// Delay that returns immediately and sums up requested time
+#[derive(Debug, Default, Clone, Copy)]
+pub struct MockDelay {
+    elapsed_us: u64,
+}
+
+impl MockDelay {
+    /// Total requested delay in microseconds
+    pub fn elapsed_us(&self) -> u64 {
+        self.elapsed_us
+    }
+}
+
+#[cfg(feature = "eh02")]
+mod eh02 {
+    use super::{Device, MockDelay, MockError};
+    use hal::blocking::delay::DelayUs;
+    use hal::blocking::i2c::Write;
+
+    impl Write for Device {
+        type Error = MockError;
+
+        fn write(&mut self, address: u8, bytes: &[u8]) -> Result<(), MockError> {
+            Device::write(self, address, bytes)
+        }
+    }
+
+    impl Write for &mut Device {
+        type Error = MockError;
+
+        fn write(&mut self, address: u8, bytes: &[u8]) -> Result<(), MockError> {
+            Device::write(self, address, bytes)
+        }
+    }
+
+    impl DelayUs<u32> for MockDelay {
+        fn delay_us(&mut self, us: u32) {
+            self.elapsed_us += us as u64;
+        }
+    }
+}
+
+#[cfg(feature = "embedded-hal-1")]
+mod eh1 {
+    use super::{Device, MockDelay, MockError};
+    use embedded_hal_1::delay::DelayNs;
+    use embedded_hal_1::i2c::{self, ErrorKind, ErrorType, I2c, NoAcknowledgeSource, Operation};
+
+    impl i2c::Error for MockError {
+        fn kind(&self) -> ErrorKind {
+            match self {
+                MockError::Nack => ErrorKind::NoAcknowledge(NoAcknowledgeSource::Address),
+                MockError::Unsupported => ErrorKind::Other,
+            }
+        }
+    }
+
+    impl ErrorType for Device {
+        type Error = MockError;
+    }
+
+    impl I2c for Device {
+        fn transaction(
+            &mut self,
+            address: u8,
+            operations: &mut [Operation<'_>],
+        ) -> Result<(), MockError> {
+            for operation in operations {
+                match operation {
+                    Operation::Write(bytes) => Device::write(self, address, bytes)?,
+                    Operation::Read(_) => return Err(MockError::Unsupported),
+                }
+            }
+            Ok(())
+        }
+    }
+
+    impl DelayNs for MockDelay {
+        fn delay_ns(&mut self, ns: u32) {
+            self.elapsed_us += (ns as u64).div_ceil(1_000);
+        }
+    }
+}
+
+#[cfg(feature = "async")]
+mod asynch {
+    use super::{Device, MockDelay, MockError};
+    use embedded_hal_async::delay::DelayNs;
+    use embedded_hal_async::i2c::{I2c, Operation};
+
+    #[cfg(not(feature = "embedded-hal-1"))]
+    mod error {
+        use super::{Device, MockError};
+        use embedded_hal_async::i2c::{self, ErrorKind, ErrorType, NoAcknowledgeSource};
+
+        impl i2c::Error for MockError {
+            fn kind(&self) -> ErrorKind {
+                match self {
+                    MockError::Nack => ErrorKind::NoAcknowledge(NoAcknowledgeSource::Address),
+                    MockError::Unsupported => ErrorKind::Other,
+                }
+            }
+        }
+
+        impl ErrorType for Device {
+            type Error = MockError;
+        }
+    }
+
+    impl I2c for Device {
+        async fn transaction(
+            &mut self,
+            address: u8,
+            operations: &mut [Operation<'_>],
+        ) -> Result<(), MockError> {
+            for operation in operations {
+                match operation {
+                    Operation::Write(bytes) => Device::write(self, address, bytes)?,
+                    Operation::Read(_) => return Err(MockError::Unsupported),
+                }
+            }
+            Ok(())
+        }
+    }
+
+    impl DelayNs for MockDelay {
+        async fn delay_ns(&mut self, ns: u32) {
+            self.elapsed_us += (ns as u64).div_ceil(1_000);
+        }
+    }
+}
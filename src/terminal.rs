@@ -0,0 +1,222 @@
+//! Terminal backend drawing a simulated panel with ANSI escape sequences.
+//!
+//! `Terminal` wraps a [`mock::Device`](crate::mock::Device) and redraws the
+//! panel after every bus transaction, so the regular `ST7032i` API can be
+//! used to iterate on UI code without hardware. The cursor is underlined,
+//! the blinking block is drawn in reverse video and custom characters are
+//! shown in the panel as their slot number, with the CGRAM content drawn as
+//! 5x8 block art below the panel.
+
+use crate::charset;
+use crate::mock::{Device, MockError};
+use std::io;
+use std::string::String;
+use std::thread;
+use std::time::Duration;
+
+/// Panel colours: black on green
+const PANEL: &str = "\x1b[30;102m";
+/// Custom characters: magenta on green
+const GLYPH: &str = "\x1b[35;102m";
+const UNDERLINE: &str = "\x1b[4m";
+const BLINK: &str = "\x1b[5;7m";
+const RESET: &str = "\x1b[0m";
+
+/// Terminal errors
+#[derive(Debug)]
+pub enum TerminalError {
+    /// Error reported by the simulated device
+    Device(MockError),
+    /// Error writing to the terminal
+    Io(io::Error),
+}
+
+/// Simulated panel drawn to `W`
+#[derive(Debug)]
+pub struct Terminal<W: io::Write> {
+    device: Device,
+    out: W,
+    lines: usize,
+}
+
+impl<W: io::Write> Terminal<W> {
+    /// Draw `device` to `out`
+    pub fn new(device: Device, out: W) -> Self {
+        Terminal {
+            device,
+            out,
+            lines: 0,
+        }
+    }
+
+    /// Simulated device
+    pub fn device(&self) -> &Device {
+        &self.device
+    }
+
+    /// Destroy terminal and return the device and the writer
+    pub fn release(self) -> (Device, W) {
+        (self.device, self.out)
+    }
+
+    /// Process one I²C write transaction and redraw the panel
+    pub fn write(&mut self, address: u8, bytes: &[u8]) -> Result<(), TerminalError> {
+        self.device
+            .write(address, bytes)
+            .map_err(TerminalError::Device)?;
+        self.draw().map_err(TerminalError::Io)
+    }
+
+    /// Redraw the panel over the previous frame
+    pub fn draw(&mut self) -> io::Result<()> {
+        let frame = frame(&self.device);
+        if self.lines > 0 {
+            write!(self.out, "\x1b[{}A", self.lines)?;
+        }
+        for line in frame.lines() {
+            writeln!(self.out, "\x1b[2K{}", line)?;
+        }
+        self.lines = frame.lines().count();
+        self.out.flush()
+    }
+}
+
+/// Render panel and CGRAM strip of `device` as ANSI text
+pub fn frame(device: &Device) -> String {
+    let cols = device.size().cols as usize;
+    let cursor = device.visible_cursor();
+    let mut frame = String::new();
+
+    frame.push('┌');
+    frame.extend(core::iter::repeat_n('─', cols));
+    frame.push_str("┐\n");
+    for row in 0..device.rows() {
+        frame.push('│');
+        for (col, code) in device.row(row).enumerate() {
+            frame.push_str(PANEL);
+            if cursor == Some((row, col as u8)) && device.display_on() {
+                if device.cursor() {
+                    frame.push_str(UNDERLINE);
+                }
+                if device.blink() {
+                    frame.push_str(BLINK);
+                }
+            }
+            if !device.display_on() {
+                frame.push(' ');
+            } else if code < 0x10 {
+                frame.push_str(GLYPH);
+                frame.push(char::from(b'0' + (code & 0x07)));
+            } else {
+                frame.push(charset::decode(code).unwrap_or('?'));
+            }
+            frame.push_str(RESET);
+        }
+        frame.push_str("│\n");
+    }
+    frame.push('└');
+    frame.extend(core::iter::repeat_n('─', cols));
+    frame.push_str("┘\n");
+
+    for slot in 0..8 {
+        frame.push_str("  ");
+        frame.push(char::from(b'0' + slot));
+        frame.push_str("   ");
+    }
+    frame.push('\n');
+    for pair in 0..4 {
+        for slot in 0..8 {
+            let glyph = device.glyph(slot);
+            let (upper, lower) = (glyph[pair * 2], glyph[pair * 2 + 1]);
+            for bit in (0..5).rev() {
+                frame.push(match (upper >> bit & 1, lower >> bit & 1) {
+                    (1, 1) => '█',
+                    (1, 0) => '▀',
+                    (0, 1) => '▄',
+                    _ => '·',
+                });
+            }
+            frame.push(' ');
+        }
+        frame.push('\n');
+    }
+    frame
+}
+
+/// Delay sleeping the current thread, so animations run in real time
+#[derive(Debug, Default, Clone, Copy)]
+pub struct StdDelay;
+
+impl StdDelay {
+    fn sleep_us(us: u32) {
+        thread::sleep(Duration::from_micros(us as u64));
+    }
+}
+
+#[cfg(feature = "eh02")]
+mod eh02 {
+    use super::{StdDelay, Terminal, TerminalError};
+    use hal::blocking::delay::DelayUs;
+    use hal::blocking::i2c::Write;
+    use std::io;
+
+    impl<W: io::Write> Write for Terminal<W> {
+        type Error = TerminalError;
+
+        fn write(&mut self, address: u8, bytes: &[u8]) -> Result<(), TerminalError> {
+            Terminal::write(self, address, bytes)
+        }
+    }
+
+    impl DelayUs<u32> for StdDelay {
+        fn delay_us(&mut self, us: u32) {
+            StdDelay::sleep_us(us)
+        }
+    }
+}
+
+#[cfg(feature = "embedded-hal-1")]
+mod eh1 {
+    use super::{StdDelay, Terminal, TerminalError};
+    use crate::mock::MockError;
+    use embedded_hal_1::delay::DelayNs;
+    use embedded_hal_1::i2c::{Error, ErrorKind, ErrorType, I2c, Operation};
+    use std::io;
+
+    impl Error for TerminalError {
+        fn kind(&self) -> ErrorKind {
+            match self {
+                TerminalError::Device(error) => error.kind(),
+                TerminalError::Io(_) => ErrorKind::Other,
+            }
+        }
+    }
+
+    impl<W: io::Write> ErrorType for Terminal<W> {
+        type Error = TerminalError;
+    }
+
+    impl<W: io::Write> I2c for Terminal<W> {
+        fn transaction(
+            &mut self,
+            address: u8,
+            operations: &mut [Operation<'_>],
+        ) -> Result<(), TerminalError> {
+            for operation in operations {
+                match operation {
+                    Operation::Write(bytes) => Terminal::write(self, address, bytes)?,
+                    Operation::Read(_) => {
+                        return Err(TerminalError::Device(MockError::Unsupported))
+                    }
+                }
+            }
+            Ok(())
+        }
+    }
+
+    impl DelayNs for StdDelay {
+        fn delay_ns(&mut self, ns: u32) {
+            StdDelay::sleep_us(ns.div_ceil(1_000))
+        }
+    }
+}
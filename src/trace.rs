@@ -0,0 +1,223 @@
+//! Recording of the bus traffic and delays produced by the driver.
+//!
+//! `TraceI2c` and `TraceDelay` wrap a bus and a delay and append every
+//! transaction and every requested delay to a shared [`Trace`]. The trace
+//! formats as text, one event per line, so it can be saved and diffed:
+//!
+//! ```text
+//! i2c 3e 80:38
+//! delay 1000
+//! i2c 3e 40:48 65 6c 6c 6f
+//! ```
+//!
+//! I²C lines list the address followed by the transaction split into
+//! control bytes and their payload: a control byte with the Co bit set is
+//! followed by a single byte, otherwise the rest of the transaction is the
+//! payload.
+//!
+//! ```
+//! use st7032i::mock::{Device, MockDelay};
+//! use st7032i::trace::{Trace, TraceDelay, TraceI2c};
+//! use st7032i::{DisplaySize, ST7032i};
+//!
+//! let size = DisplaySize::new(16, 2).unwrap();
+//! let trace = Trace::new();
+//! let i2c = TraceI2c::new(Device::new(size), &trace);
+//! let delay = TraceDelay::new(MockDelay::default(), &trace);
+//! let mut display = ST7032i::new(i2c, delay, size);
+//! display.clear().unwrap();
+//! assert_eq!(trace.to_string(), "i2c 3e 80:01\ndelay 2000\n");
+//! ```
+
+use core::cell::RefCell;
+use core::fmt;
+use std::rc::Rc;
+use std::vec::Vec;
+
+/// Recorded event
+#[derive(Debug, PartialEq, Clone)]
+pub enum Event {
+    /// I²C write transaction
+    Write { address: u8, bytes: Vec<u8> },
+    /// Requested delay in microseconds
+    Delay(u32),
+}
+
+impl fmt::Display for Event {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Event::Write { address, bytes } => {
+                write!(f, "i2c {:02x}", address)?;
+                let mut iter = bytes.iter();
+                while let Some(control) = iter.next() {
+                    write!(f, " {:02x}:", control)?;
+                    let payload: Vec<u8> = if control & 0b_10000000 != 0 {
+                        iter.next().into_iter().copied().collect()
+                    } else {
+                        iter.by_ref().copied().collect()
+                    };
+                    for (idx, byte) in payload.iter().enumerate() {
+                        if idx > 0 {
+                            f.write_str(" ")?;
+                        }
+                        write!(f, "{:02x}", byte)?;
+                    }
+                }
+                Ok(())
+            }
+            Event::Delay(us) => write!(f, "delay {}", us),
+        }
+    }
+}
+
+/// Event log shared by `TraceI2c` and `TraceDelay`
+#[derive(Debug, Default, Clone)]
+pub struct Trace {
+    events: Rc<RefCell<Vec<Event>>>,
+}
+
+impl Trace {
+    /// Create empty trace
+    pub fn new() -> Self {
+        Trace::default()
+    }
+
+    /// Recorded events
+    pub fn events(&self) -> Vec<Event> {
+        self.events.borrow().clone()
+    }
+
+    /// Drop recorded events
+    pub fn clear(&self) {
+        self.events.borrow_mut().clear();
+    }
+
+    fn push(&self, event: Event) {
+        self.events.borrow_mut().push(event);
+    }
+}
+
+impl fmt::Display for Trace {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        for event in self.events.borrow().iter() {
+            writeln!(f, "{}", event)?;
+        }
+        Ok(())
+    }
+}
+
+/// I²C bus recording transactions before passing them on
+#[derive(Debug)]
+pub struct TraceI2c<I2C> {
+    inner: I2C,
+    trace: Trace,
+}
+
+impl<I2C> TraceI2c<I2C> {
+    /// Record transactions on `inner` to `trace`
+    pub fn new(inner: I2C, trace: &Trace) -> Self {
+        TraceI2c {
+            inner,
+            trace: trace.clone(),
+        }
+    }
+
+    /// Destroy wrapper and return the bus
+    pub fn release(self) -> I2C {
+        self.inner
+    }
+
+    fn record(&self, address: u8, bytes: &[u8]) {
+        self.trace.push(Event::Write {
+            address,
+            bytes: bytes.to_vec(),
+        });
+    }
+}
+
+/// Delay recording requested time before passing it on
+#[derive(Debug)]
+pub struct TraceDelay<D> {
+    inner: D,
+    trace: Trace,
+}
+
+impl<D> TraceDelay<D> {
+    /// Record delays requested from `inner` to `trace`
+    pub fn new(inner: D, trace: &Trace) -> Self {
+        TraceDelay {
+            inner,
+            trace: trace.clone(),
+        }
+    }
+
+    /// Destroy wrapper and return the delay
+    pub fn release(self) -> D {
+        self.inner
+    }
+
+    fn record(&self, us: u32) {
+        self.trace.push(Event::Delay(us));
+    }
+}
+
+#[cfg(feature = "eh02")]
+mod eh02 {
+    use super::{TraceDelay, TraceI2c};
+    use hal::blocking::delay::DelayUs;
+    use hal::blocking::i2c::Write;
+
+    impl<I2C: Write> Write for TraceI2c<I2C> {
+        type Error = I2C::Error;
+
+        fn write(&mut self, address: u8, bytes: &[u8]) -> Result<(), I2C::Error> {
+            self.record(address, bytes);
+            self.inner.write(address, bytes)
+        }
+    }
+
+    impl<D: DelayUs<u32>> DelayUs<u32> for TraceDelay<D> {
+        fn delay_us(&mut self, us: u32) {
+            self.record(us);
+            self.inner.delay_us(us)
+        }
+    }
+}
+
+#[cfg(feature = "embedded-hal-1")]
+mod eh1 {
+    use super::{TraceDelay, TraceI2c};
+    use embedded_hal_1::delay::DelayNs;
+    use embedded_hal_1::i2c::{ErrorType, I2c, Operation};
+
+    impl<I2C: I2c> ErrorType for TraceI2c<I2C> {
+        type Error = I2C::Error;
+    }
+
+    impl<I2C: I2c> I2c for TraceI2c<I2C> {
+        fn transaction(
+            &mut self,
+            address: u8,
+            operations: &mut [Operation<'_>],
+        ) -> Result<(), I2C::Error> {
+            for operation in operations.iter() {
+                if let Operation::Write(bytes) = operation {
+                    self.record(address, bytes);
+                }
+            }
+            self.inner.transaction(address, operations)
+        }
+    }
+
+    impl<D: DelayNs> DelayNs for TraceDelay<D> {
+        fn delay_ns(&mut self, ns: u32) {
+            self.record(ns.div_ceil(1_000));
+            self.inner.delay_ns(ns)
+        }
+
+        fn delay_us(&mut self, us: u32) {
+            self.record(us);
+            self.inner.delay_us(us)
+        }
+    }
+}
@@ -0,0 +1,100 @@
+mod common;
+
+use core::future::Future;
+use core::pin::pin;
+use core::task::{Context, Poll, Waker};
+use st7032i::mock::{Device, MockDelay, Target};
+use st7032i::{DisplaySize, Error, ST7032iAsync};
+
+const SIZE: DisplaySize = DisplaySize::new(16, 2).unwrap();
+
+type Display = ST7032iAsync<Device, MockDelay>;
+
+/// Poll `future` to completion, the mock never returns `Pending`
+fn block_on<F: Future>(future: F) -> F::Output {
+    let mut future = pin!(future);
+    let mut cx = Context::from_waker(Waker::noop());
+    loop {
+        if let Poll::Ready(output) = future.as_mut().poll(&mut cx) {
+            return output;
+        }
+    }
+}
+
+fn display() -> Display {
+    let mut display = ST7032iAsync::new(Device::new(SIZE), MockDelay::default(), SIZE);
+    block_on(display.init()).unwrap();
+    display
+}
+
+#[test]
+fn init() {
+    let (device, _) = display().release();
+    let expected = common::device(common::display(SIZE));
+    assert!(device.display_on());
+    assert!(device.two_lines());
+    assert!(!device.extended());
+    assert!(device.increment());
+    assert_eq!(device.address_counter(), 0);
+    assert_eq!(device.contrast(), expected.contrast());
+    assert_eq!(device.osc_frequency(), expected.osc_frequency());
+    assert_eq!(device.follower(), expected.follower());
+    assert_eq!(device.booster(), expected.booster());
+}
+
+#[test]
+fn move_cursor_and_write_str() {
+    let mut display = display();
+    block_on(display.move_cursor(1, 3)).unwrap();
+    block_on(display.write_str("Hi¥")).unwrap();
+    assert_eq!(block_on(display.move_cursor(2, 0)), Err(Error::OutOfRange));
+    assert_eq!(block_on(display.move_cursor(0, 16)), Err(Error::OutOfRange));
+
+    let (device, _) = display.release();
+    assert_eq!(common::row(&device, 1)[3..6], [b'H', b'i', 0x5c]);
+    assert_eq!(device.address_counter(), 0x46);
+}
+
+#[test]
+fn write_str_fallback() {
+    let mut display = display();
+    block_on(display.write_str("a€")).unwrap();
+    display.set_fallback(None);
+    assert_eq!(
+        block_on(display.write_str("€")),
+        Err(Error::UnsupportedChar)
+    );
+
+    let (device, _) = display.release();
+    assert_eq!(common::row(&device, 0)[..3], [b'a', b'?', b' ']);
+}
+
+#[test]
+fn long_string() {
+    let mut display = display();
+    let text = "0123456789".repeat(5);
+    block_on(display.write_str(&text)).unwrap();
+
+    let (device, _) = display.release();
+    assert_eq!(device.ddram()[..40], text.as_bytes()[..40]);
+    assert_eq!(device.ddram()[0x40..0x4a], text.as_bytes()[40..]);
+}
+
+#[test]
+fn create_char() {
+    let glyph = [0x00, 0x0a, 0x1f, 0x1f, 0x0e, 0x04, 0x00, 0x00];
+    let mut display = display();
+    block_on(display.create_char(3, glyph)).unwrap();
+
+    block_on(display.move_cursor(1, 2)).unwrap();
+    block_on(display.write_str("ab")).unwrap();
+    block_on(display.create_char(4, [0x1f; 8])).unwrap();
+    block_on(display.write_str("c")).unwrap();
+
+    let (device, _) = display.release();
+    assert_eq!(device.glyph(3), glyph);
+    assert_eq!(device.glyph(4), [0x1f; 8]);
+    assert_eq!(device.glyph(2), [0; 8]);
+    assert_eq!(device.target(), Target::Ddram);
+    assert_eq!(common::row(&device, 1)[..5], *b"  abc");
+}
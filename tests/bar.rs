@@ -0,0 +1,95 @@
+mod common;
+
+use st7032i::{DisplaySize, Error, GlyphManager, HorizontalBar, VerticalBar};
+
+const SIZE: DisplaySize = DisplaySize::new(8, 2).unwrap();
+
+#[test]
+fn horizontal() {
+    let mut display = common::display(SIZE);
+    let mut glyphs = GlyphManager::new();
+    let bar = HorizontalBar::new(1, 2, 4);
+
+    bar.draw(&mut display, &mut glyphs, 13, 20).unwrap();
+    let device = common::device(display);
+    assert_eq!(
+        common::row(&device, 1),
+        [b' ', b' ', 0xff, 0xff, 0, b' ', b' ', b' ']
+    );
+    assert_eq!(device.glyph(0), [0x1c; 8]);
+}
+
+#[test]
+fn horizontal_bounds() {
+    let mut display = common::display(SIZE);
+    let mut glyphs = GlyphManager::new();
+    let bar = HorizontalBar::new(0, 0, 8);
+
+    bar.draw(&mut display, &mut glyphs, 100, 20).unwrap();
+    assert_eq!(common::row(&common::device(display), 0), [0xff; 8]);
+}
+
+#[test]
+fn vertical() {
+    let mut display = common::display(SIZE);
+    let mut glyphs = GlyphManager::new();
+    let bar = VerticalBar::new(0, 1, 4, 2);
+
+    bar.draw(&mut display, &mut glyphs, &[16, 11, 5], 16)
+        .unwrap();
+    let device = common::device(display);
+    assert_eq!(
+        common::row(&device, 0),
+        [b' ', 0xff, 0, b' ', b' ', b' ', b' ', b' ']
+    );
+    assert_eq!(
+        common::row(&device, 1),
+        [b' ', 0xff, 0xff, 1, b' ', b' ', b' ', b' ']
+    );
+    assert_eq!(
+        device.glyph(0),
+        [0x00, 0x00, 0x00, 0x00, 0x00, 0x1f, 0x1f, 0x1f]
+    );
+    assert_eq!(
+        device.glyph(1),
+        [0x00, 0x00, 0x00, 0x1f, 0x1f, 0x1f, 0x1f, 0x1f]
+    );
+}
+
+#[test]
+fn out_of_range() {
+    let mut display = common::display(SIZE);
+    let mut glyphs = GlyphManager::new();
+    for n in 0..8 {
+        glyphs.load(&mut display, &[n; 8]).unwrap();
+    }
+
+    for bar in [HorizontalBar::new(0, 6, 4), HorizontalBar::new(2, 0, 4)].iter() {
+        assert_eq!(
+            bar.draw(&mut display, &mut glyphs, 13, 20),
+            Err(Error::OutOfRange)
+        );
+    }
+    display.set_double_height(true).unwrap();
+    assert_eq!(
+        HorizontalBar::new(1, 0, 4).draw(&mut display, &mut glyphs, 13, 20),
+        Err(Error::OutOfRange)
+    );
+    assert_eq!(
+        VerticalBar::new(0, 0, 2, 2).draw(&mut display, &mut glyphs, &[5, 11], 16),
+        Err(Error::OutOfRange)
+    );
+    display.set_double_height(false).unwrap();
+    for bar in [VerticalBar::new(0, 7, 2, 1), VerticalBar::new(1, 0, 2, 2)].iter() {
+        assert_eq!(
+            bar.draw(&mut display, &mut glyphs, &[5, 11], 16),
+            Err(Error::OutOfRange)
+        );
+    }
+
+    let device = common::device(display);
+    for n in 0..8 {
+        assert_eq!(glyphs.code(&[n; 8]), Some(n));
+        assert_eq!(device.glyph(n), [n; 8]);
+    }
+}
@@ -0,0 +1,87 @@
+mod common;
+
+use st7032i::trace::{Event, Trace};
+use st7032i::{DisplaySize, Error, GlyphManager};
+
+const SIZE: DisplaySize = DisplaySize::new(16, 2).unwrap();
+
+/// Whether `trace` holds a Set CGRAM Address instruction
+fn loads_glyphs(trace: &Trace) -> bool {
+    trace.events().iter().any(|event| match event {
+        Event::Write { bytes, .. } => bytes[0] == 0x80 && bytes[1] & 0xc0 == 0x40,
+        _ => false,
+    })
+}
+
+#[test]
+fn digits() {
+    let mut display = common::display(SIZE);
+    let mut glyphs = GlyphManager::new();
+    display.write_big("12:5", 2, &mut glyphs).unwrap();
+
+    let device = common::device(display);
+    let top: Vec<u8> = device.row(0).collect();
+    let bottom: Vec<u8> = device.row(1).collect();
+    assert_eq!(
+        top[..12],
+        [b' ', b' ', 0, 1, b' ', 2, 2, 1, 0xa5, 0xff, 2, 2]
+    );
+    assert_eq!(
+        bottom[..12],
+        [b' ', b' ', 3, 0xff, 3, 4, 3, 3, 0xa5, 3, 3, 5]
+    );
+    assert_eq!(
+        device.glyph(0),
+        [0x1f, 0x1f, 0x1f, 0x00, 0x00, 0x00, 0x00, 0x00]
+    );
+    assert_eq!(
+        device.glyph(5),
+        [0x1f, 0x1f, 0x1f, 0x1f, 0x1f, 0x1f, 0x1e, 0x1c]
+    );
+}
+
+#[test]
+fn glyphs_loaded_once() {
+    let trace = Trace::new();
+    let mut display = common::traced(SIZE, &trace);
+    let mut glyphs = GlyphManager::new();
+
+    display.write_big("-1.5", 0, &mut glyphs).unwrap();
+    assert!(loads_glyphs(&trace));
+    trace.clear();
+    display.write_big("-5.1", 0, &mut glyphs).unwrap();
+    assert!(!loads_glyphs(&trace));
+}
+
+#[test]
+fn shares_glyph_manager() {
+    let mut display = common::display(SIZE);
+    let mut glyphs = GlyphManager::new();
+    let arrow = [0x04, 0x0e, 0x1f, 0x04, 0x04, 0x04, 0x04, 0x00];
+    assert_eq!(glyphs.load(&mut display, &arrow), Ok(0));
+
+    display.write_big("1", 0, &mut glyphs).unwrap();
+    assert_eq!(glyphs.code(&arrow), Some(0));
+    assert_eq!(common::row(&common::device(display), 0)[..3], [1, 2, b' ']);
+}
+
+#[test]
+fn errors() {
+    let mut display = common::display(SIZE);
+    let mut glyphs = GlyphManager::new();
+    assert_eq!(
+        display.write_big("12345", 2, &mut glyphs),
+        Err(Error::OutOfRange)
+    );
+    assert_eq!(
+        display.write_big("1a", 0, &mut glyphs),
+        Err(Error::UnsupportedChar)
+    );
+
+    let size = DisplaySize::new(16, 1).unwrap();
+    let mut display = common::display(size);
+    assert_eq!(
+        display.write_big("1", 0, &mut glyphs),
+        Err(Error::InvalidArgument)
+    );
+}
@@ -0,0 +1,119 @@
+mod common;
+
+use core::cell::Cell;
+use core::fmt::Write;
+use st7032i::bus::I2cBus;
+use st7032i::mock::{Device, MockDelay, MockError};
+use st7032i::trace::Trace;
+use st7032i::{BufferedDisplay, Config, DisplaySize, Error, ST7032i};
+
+const SIZE: DisplaySize = DisplaySize::new(8, 2).unwrap();
+
+#[test]
+fn flush_sends_changed_runs() {
+    let trace = Trace::new();
+    let mut buffered = BufferedDisplay::new(common::traced(SIZE, &trace));
+    write!(buffered, "Temp").unwrap();
+    buffered.move_cursor(1, 2).unwrap();
+    write!(buffered, "21.5").unwrap();
+    buffered.flush().unwrap();
+
+    trace.clear();
+    buffered.flush().unwrap();
+    assert_eq!(trace.to_string(), "");
+
+    buffered.move_cursor(1, 4).unwrap();
+    write!(buffered, ".7").unwrap();
+    buffered.set_char(0, 0, b't').unwrap();
+    buffered.flush().unwrap();
+    assert_eq!(
+        trace.to_string(),
+        "i2c 3e 80:80\ndelay 1000\ni2c 3e 40:74\ndelay 1000\n\
+         i2c 3e 80:c5\ndelay 1000\ni2c 3e 40:37\ndelay 1000\n"
+    );
+
+    let device = common::traced_device(buffered.release());
+    assert_eq!(device.render(), "temp    \n  21.7  ");
+}
+
+#[test]
+fn invalidate() {
+    let trace = Trace::new();
+    let config = Config::default().osc_frequency(5);
+    let mut buffered = BufferedDisplay::new(common::traced_with(SIZE, config, &trace));
+    write!(buffered, "Temp").unwrap();
+    buffered.flush().unwrap();
+
+    buffered.display().clear().unwrap();
+    trace.clear();
+    buffered.invalidate();
+    buffered.flush().unwrap();
+    assert_eq!(
+        trace.to_string(),
+        "i2c 3e 80:80\ndelay 1000\ni2c 3e 40:54 65 6d 70 20 20 20 20\ndelay 1000\n\
+         i2c 3e 80:c0\ndelay 1000\ni2c 3e 40:20 20 20 20 20 20 20 20\ndelay 1000\n"
+    );
+
+    let device = common::traced_device(buffered.release());
+    assert_eq!(device.render(), "Temp    \n        ");
+}
+
+#[test]
+fn largest_size() {
+    let size = DisplaySize::new(80, 1).unwrap();
+    let mut buffered = BufferedDisplay::new(common::display(size));
+    buffered.clear();
+    buffered.move_cursor(0, 76).unwrap();
+    write!(buffered, "edge!").unwrap();
+    buffered.flush().unwrap();
+    let device = common::device(buffered.release());
+    assert!(device.render().ends_with(" edge"));
+}
+
+/// Device not acknowledging the `nack`th transaction from now on
+struct Flaky<'a> {
+    device: Device,
+    nack: &'a Cell<Option<u32>>,
+}
+
+impl I2cBus for Flaky<'_> {
+    type Error = MockError;
+
+    fn write(&mut self, address: u8, bytes: &[u8]) -> Result<(), MockError> {
+        match self.nack.get() {
+            Some(1) => {
+                self.nack.set(None);
+                return Err(MockError::Nack);
+            }
+            Some(n) => self.nack.set(Some(n - 1)),
+            None => {}
+        }
+        self.device.write(address, bytes)
+    }
+}
+
+#[test]
+fn flush_retries_after_bus_error() {
+    // Fail the address instruction, then the data of the first run
+    for &nack in [1, 2].iter() {
+        let cell = Cell::new(None);
+        let flaky = Flaky {
+            device: Device::new(SIZE),
+            nack: &cell,
+        };
+        let mut display = ST7032i::new(flaky, MockDelay::default(), SIZE);
+        display.init().unwrap();
+        let mut buffered = BufferedDisplay::new(display);
+        buffered.flush().unwrap();
+
+        write!(buffered, "Temp").unwrap();
+        buffered.move_cursor(1, 2).unwrap();
+        write!(buffered, "21.5").unwrap();
+        cell.set(Some(nack));
+        assert_eq!(buffered.flush(), Err(Error::Bus(MockError::Nack)));
+        buffered.flush().unwrap();
+
+        let device = buffered.release().release().0.device;
+        assert_eq!(device.render(), "Temp    \n  21.5  ");
+    }
+}
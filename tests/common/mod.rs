@@ -0,0 +1,57 @@
+#![allow(dead_code)]
+
+use st7032i::interface::I2cInterface;
+use st7032i::mock::{Device, MockDelay};
+use st7032i::trace::{Trace, TraceDelay, TraceI2c};
+use st7032i::{Config, DisplaySize, ST7032i};
+
+pub type Display = ST7032i<I2cInterface<Device>, MockDelay>;
+pub type TracedDisplay = ST7032i<I2cInterface<TraceI2c<Device>>, TraceDelay<MockDelay>>;
+
+/// Initialized display driving a simulated device
+pub fn display(size: DisplaySize) -> Display {
+    let mut display = ST7032i::new(Device::new(size), MockDelay::default(), size);
+    display.init().unwrap();
+    display
+}
+
+/// Initialized display recording to `trace`, the init sequence is dropped
+pub fn traced(size: DisplaySize, trace: &Trace) -> TracedDisplay {
+    let mut display = uninit_traced(size, trace);
+    display.init().unwrap();
+    trace.clear();
+    display
+}
+
+/// Initialized display with custom `config` recording to `trace`,
+/// the init sequence is dropped
+pub fn traced_with(size: DisplaySize, config: Config, trace: &Trace) -> TracedDisplay {
+    let i2c = TraceI2c::new(Device::new(size), trace);
+    let delay = TraceDelay::new(MockDelay::default(), trace);
+    let mut display = ST7032i::with_config(i2c, delay, size, config);
+    display.init().unwrap();
+    trace.clear();
+    display
+}
+
+/// Display recording to `trace` before `init`
+pub fn uninit_traced(size: DisplaySize, trace: &Trace) -> TracedDisplay {
+    let i2c = TraceI2c::new(Device::new(size), trace);
+    let delay = TraceDelay::new(MockDelay::default(), trace);
+    ST7032i::new(i2c, delay, size)
+}
+
+/// Simulated device behind the display
+pub fn device(display: Display) -> Device {
+    display.release().0
+}
+
+/// Simulated device behind the traced display
+pub fn traced_device(display: TracedDisplay) -> Device {
+    display.release().0.release()
+}
+
+/// Character codes visible in `row`
+pub fn row(device: &Device, row: u8) -> Vec<u8> {
+    device.row(row).collect()
+}
@@ -0,0 +1,114 @@
+mod common;
+
+use core::fmt::Write;
+use st7032i::mock::Target;
+use st7032i::{Direction, DisplaySize};
+
+#[test]
+fn write_and_move() {
+    let mut display = common::display(DisplaySize::new(16, 2).unwrap());
+    assert_eq!(display.cursor_position(), (0, 0));
+    write!(display, "abc").unwrap();
+    assert_eq!(display.cursor_position(), (0, 3));
+    display.move_cursor(1, 5).unwrap();
+    display.write_bytes(b"xy").unwrap();
+    assert_eq!(display.cursor_position(), (1, 7));
+    display.shift_cursor(Direction::RightToLeft).unwrap();
+    assert_eq!(display.cursor_position(), (1, 6));
+    display.home().unwrap();
+    assert_eq!(display.cursor_position(), (0, 0));
+}
+
+#[test]
+fn line_wrap() {
+    let mut display = common::display(DisplaySize::new(40, 2).unwrap());
+    display.move_cursor(0, 38).unwrap();
+    write!(display, "abc").unwrap();
+    assert_eq!(display.cursor_position(), (1, 1));
+    display.move_cursor(1, 39).unwrap();
+    write!(display, "d").unwrap();
+    assert_eq!(display.cursor_position(), (0, 0));
+
+    let mut display = common::display(DisplaySize::new(80, 1).unwrap());
+    display.move_cursor(0, 79).unwrap();
+    write!(display, "e").unwrap();
+    assert_eq!(display.cursor_position(), (0, 0));
+}
+
+#[test]
+fn right_to_left() {
+    let mut display = common::display(DisplaySize::new(16, 2).unwrap());
+    display.enable_scroll(Direction::RightToLeft).unwrap();
+    display.disable_scroll().unwrap();
+    display.move_cursor(0, 5).unwrap();
+    write!(display, "ab").unwrap();
+    assert_eq!(display.cursor_position(), (0, 3));
+    display.clear().unwrap();
+    display.move_cursor(1, 0).unwrap();
+    write!(display, "c").unwrap();
+    assert_eq!(display.cursor_position(), (0, 39));
+
+    let device = common::device(display);
+    assert!(!device.increment());
+    assert_eq!(device.address_counter(), 0x27);
+}
+
+#[test]
+fn autoscroll() {
+    let mut display = common::display(DisplaySize::new(16, 2).unwrap());
+    display.enable_scroll(Direction::LeftToRigh).unwrap();
+    write!(display, "abcd").unwrap();
+    assert_eq!(display.cursor_position(), (0, 4));
+    assert_eq!(display.display_shift(), 4);
+    assert_eq!(display.visible_cursor(), Some((0, 0)));
+
+    let device = common::device(display);
+    assert_eq!(device.address_counter(), 4);
+    assert_eq!(device.shift(), 4);
+    assert_eq!(device.visible_cursor(), Some((0, 0)));
+}
+
+#[test]
+fn shift_display() {
+    let mut display = common::display(DisplaySize::new(16, 2).unwrap());
+    display.move_cursor(1, 2).unwrap();
+    display.shift_display(Direction::LeftToRigh).unwrap();
+    assert_eq!(display.display_shift(), 39);
+    assert_eq!(display.visible_cursor(), Some((1, 3)));
+    for _ in 0..3 {
+        display.shift_display(Direction::RightToLeft).unwrap();
+    }
+    assert_eq!(display.cursor_position(), (1, 2));
+    assert_eq!(display.display_shift(), 2);
+    assert_eq!(display.visible_cursor(), Some((1, 0)));
+    display.shift_display(Direction::RightToLeft).unwrap();
+    assert_eq!(display.visible_cursor(), None);
+
+    let device = common::device(display);
+    assert_eq!(device.shift(), 3);
+    assert_eq!(device.visible_cursor(), None);
+
+    let mut display = common::display(DisplaySize::new(16, 2).unwrap());
+    display.shift_display(Direction::RightToLeft).unwrap();
+    display.home().unwrap();
+    assert_eq!(display.display_shift(), 0);
+    assert_eq!(common::device(display).shift(), 0);
+}
+
+#[test]
+fn restored_after_cgram_and_icons() {
+    let mut display = common::display(DisplaySize::new(16, 2).unwrap());
+    display.move_cursor(1, 2).unwrap();
+    write!(display, "a").unwrap();
+    display.create_char(1, [0x1f; 8]).unwrap();
+    display.set_icon(3, 1, true).unwrap();
+    display.write_icons(&[0x01; 16]).unwrap();
+    assert_eq!(display.cursor_position(), (1, 3));
+    write!(display, "b").unwrap();
+
+    let device = common::device(display);
+    assert_eq!(device.target(), Target::Ddram);
+    assert_eq!(device.address_counter(), 0x44);
+    assert_eq!(device.render().lines().nth(1), Some("  ab            "));
+    assert_eq!(device.glyph(1), [0x1f; 8]);
+}
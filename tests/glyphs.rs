@@ -0,0 +1,59 @@
+mod common;
+
+use st7032i::trace::{Event, Trace};
+use st7032i::{DisplaySize, Glyph, GlyphManager};
+
+const SIZE: DisplaySize = DisplaySize::new(16, 2).unwrap();
+
+fn glyph(n: u8) -> Glyph {
+    [n, n, n, n, n, n, n, n]
+}
+
+#[test]
+fn allocate_and_reuse() {
+    let trace = Trace::new();
+    let mut display = common::traced(SIZE, &trace);
+    let mut glyphs = GlyphManager::new();
+
+    assert_eq!(glyphs.load(&mut display, &glyph(1)), Ok(0));
+    assert_eq!(glyphs.load(&mut display, &glyph(2)), Ok(1));
+    trace.clear();
+    assert_eq!(glyphs.load(&mut display, &glyph(1)), Ok(0));
+    assert_eq!(trace.events(), Vec::<Event>::new());
+    assert_eq!(glyphs.code(&glyph(2)), Some(1));
+    assert_eq!(glyphs.code(&glyph(3)), None);
+
+    let device = common::traced_device(display);
+    assert_eq!(device.glyph(0), glyph(1));
+    assert_eq!(device.glyph(1), glyph(2));
+}
+
+#[test]
+fn evict_least_recently_used() {
+    let mut display = common::display(SIZE);
+    let mut glyphs = GlyphManager::new();
+
+    for n in 0..8 {
+        assert_eq!(glyphs.load(&mut display, &glyph(n)), Ok(n));
+    }
+    glyphs.load(&mut display, &glyph(0)).unwrap();
+    assert_eq!(glyphs.load(&mut display, &glyph(8)), Ok(1));
+    assert_eq!(glyphs.code(&glyph(1)), None);
+    assert_eq!(glyphs.load(&mut display, &glyph(9)), Ok(2));
+
+    let device = common::device(display);
+    assert_eq!(device.glyph(0), glyph(0));
+    assert_eq!(device.glyph(1), glyph(8));
+    assert_eq!(device.glyph(2), glyph(9));
+}
+
+#[test]
+fn reset() {
+    let mut display = common::display(SIZE);
+    let mut glyphs = GlyphManager::new();
+
+    glyphs.load(&mut display, &glyph(5)).unwrap();
+    glyphs.reset();
+    assert_eq!(glyphs.code(&glyph(5)), None);
+    assert_eq!(glyphs.load(&mut display, &glyph(6)), Ok(0));
+}
@@ -0,0 +1,73 @@
+//! Golden traces of the driver output.
+//!
+//! Run with `UPDATE_GOLDEN=1` to rewrite the files in `tests/golden` after
+//! an intended change, then review the diff.
+
+mod common;
+
+use st7032i::trace::Trace;
+use st7032i::{Config, Direction, DisplaySize};
+use std::{env, fs};
+
+const SIZE: DisplaySize = DisplaySize::new(16, 2).unwrap();
+
+fn check(name: &str, trace: &Trace) {
+    let path = format!("{}/tests/golden/{}.trace", env!("CARGO_MANIFEST_DIR"), name);
+    let actual = trace.to_string();
+    if env::var_os("UPDATE_GOLDEN").is_some() {
+        fs::write(&path, &actual).unwrap();
+    }
+    let expected = fs::read_to_string(&path).unwrap();
+    assert_eq!(actual, expected, "trace differs from {}", path);
+}
+
+#[test]
+fn init() {
+    let trace = Trace::new();
+    common::uninit_traced(SIZE, &trace).init().unwrap();
+    check("init", &trace);
+}
+
+#[test]
+fn clear() {
+    let trace = Trace::new();
+    common::traced(SIZE, &trace).clear().unwrap();
+    check("clear", &trace);
+}
+
+#[test]
+fn create_char() {
+    let trace = Trace::new();
+    common::traced(SIZE, &trace)
+        .create_char(2, [0x00, 0x0a, 0x1f, 0x1f, 0x0e, 0x04, 0x00, 0x00])
+        .unwrap();
+    check("create_char", &trace);
+}
+
+#[test]
+fn enable_scroll() {
+    let trace = Trace::new();
+    let mut display = common::traced(SIZE, &trace);
+    display.enable_scroll(Direction::LeftToRigh).unwrap();
+    display.disable_scroll().unwrap();
+    check("enable_scroll", &trace);
+}
+
+#[test]
+fn write_str() {
+    let trace = Trace::new();
+    let mut display = common::traced(SIZE, &trace);
+    display.move_cursor(1, 4).unwrap();
+    display.write_str("Hello").unwrap();
+    check("write_str", &trace);
+}
+
+#[test]
+fn write_str_streamed() {
+    let trace = Trace::new();
+    let config = Config::default().osc_frequency(5);
+    let mut display = common::traced_with(SIZE, config, &trace);
+    display.move_cursor(1, 4).unwrap();
+    display.write_str("Hello").unwrap();
+    check("write_str_streamed", &trace);
+}
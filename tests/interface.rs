@@ -0,0 +1,211 @@
+use std::cell::RefCell;
+use std::convert::Infallible;
+use std::rc::Rc;
+
+use st7032i::bus::{Delay, OutputPin, SpiBus};
+use st7032i::interface::{Interface, ParallelInterface, SpiInterface};
+
+/// Signal levels seen by the controller
+#[derive(Debug, Default)]
+struct Lines {
+    rs: bool,
+    en: bool,
+    data: [bool; 8],
+    /// RS and data lines latched on every falling edge of EN
+    strobes: Vec<(bool, u8)>,
+    /// RS level and bytes of every SPI transfer
+    spi: Vec<(bool, Vec<u8>)>,
+}
+
+#[derive(Clone, Copy)]
+enum Role {
+    Rs,
+    En,
+    Data(usize),
+}
+
+struct Pin {
+    role: Role,
+    lines: Rc<RefCell<Lines>>,
+}
+
+impl OutputPin for Pin {
+    type Error = Infallible;
+
+    fn set_high(&mut self) -> Result<(), Infallible> {
+        self.set(true);
+        Ok(())
+    }
+
+    fn set_low(&mut self) -> Result<(), Infallible> {
+        self.set(false);
+        Ok(())
+    }
+}
+
+impl Pin {
+    fn set(&mut self, level: bool) {
+        let mut lines = self.lines.borrow_mut();
+        match self.role {
+            Role::Rs => lines.rs = level,
+            Role::Data(idx) => lines.data[idx] = level,
+            Role::En => {
+                if lines.en && !level {
+                    let bits = lines
+                        .data
+                        .iter()
+                        .enumerate()
+                        .fold(0, |acc, (idx, &high)| acc | (high as u8) << idx);
+                    let rs = lines.rs;
+                    lines.strobes.push((rs, bits));
+                }
+                lines.en = level;
+            }
+        }
+    }
+}
+
+struct Spi {
+    lines: Rc<RefCell<Lines>>,
+}
+
+impl SpiBus for Spi {
+    type Error = Infallible;
+
+    fn write(&mut self, bytes: &[u8]) -> Result<(), Infallible> {
+        let mut lines = self.lines.borrow_mut();
+        let rs = lines.rs;
+        lines.spi.push((rs, bytes.to_vec()));
+        Ok(())
+    }
+}
+
+struct NoDelay;
+
+impl Delay for NoDelay {
+    fn delay_us(&mut self, _us: u32) {}
+}
+
+fn pin(lines: &Rc<RefCell<Lines>>, role: Role) -> Pin {
+    Pin {
+        role,
+        lines: lines.clone(),
+    }
+}
+
+fn pins<const N: usize>(lines: &Rc<RefCell<Lines>>) -> [Pin; N] {
+    let mut idx = 0;
+    [(); N].map(|_| {
+        idx += 1;
+        pin(lines, Role::Data(idx - 1))
+    })
+}
+
+#[test]
+fn spi() {
+    let lines = Rc::default();
+    let mut interface = SpiInterface::new(
+        Spi {
+            lines: Rc::clone(&lines),
+        },
+        pin(&lines, Role::Rs),
+    );
+    interface.init().unwrap();
+    interface.send_command(0x39).unwrap();
+    interface.send_data(b"Hi").unwrap();
+    interface.send_command(0x01).unwrap();
+
+    assert_eq!(
+        lines.borrow().spi,
+        [
+            (false, vec![0x39]),
+            (true, b"Hi".to_vec()),
+            (false, vec![0x01])
+        ]
+    );
+}
+
+#[test]
+fn parallel_4bit_wake_up() {
+    let lines = Rc::default();
+    let mut interface = ParallelInterface::new_4bit(
+        pin(&lines, Role::Rs),
+        pin(&lines, Role::En),
+        pins(&lines),
+        NoDelay,
+    );
+    interface.init().unwrap();
+
+    assert_eq!(
+        lines.borrow().strobes,
+        [(false, 0x3), (false, 0x3), (false, 0x3), (false, 0x2)]
+    );
+}
+
+#[test]
+fn parallel_4bit_nibble_order() {
+    let lines = Rc::default();
+    let mut interface = ParallelInterface::new_4bit(
+        pin(&lines, Role::Rs),
+        pin(&lines, Role::En),
+        pins(&lines),
+        NoDelay,
+    );
+    interface.send_command(0x0c).unwrap();
+    interface.send_data(&[0xa5, 0x3c]).unwrap();
+
+    assert_eq!(
+        lines.borrow().strobes,
+        [
+            (false, 0x0),
+            (false, 0xc),
+            (true, 0xa),
+            (true, 0x5),
+            (true, 0x3),
+            (true, 0xc)
+        ]
+    );
+}
+
+#[test]
+fn parallel_4bit_function_set() {
+    let lines = Rc::default();
+    let mut interface = ParallelInterface::new_4bit(
+        pin(&lines, Role::Rs),
+        pin(&lines, Role::En),
+        pins(&lines),
+        NoDelay,
+    );
+    interface.send_command(0x39).unwrap();
+    interface.send_command(0x38).unwrap();
+    // Not a Function Set, bit 4 is kept
+    interface.send_command(0x14).unwrap();
+
+    assert_eq!(
+        lines.borrow().strobes,
+        [
+            (false, 0x2),
+            (false, 0x9),
+            (false, 0x2),
+            (false, 0x8),
+            (false, 0x1),
+            (false, 0x4)
+        ]
+    );
+}
+
+#[test]
+fn parallel_8bit() {
+    let lines = Rc::default();
+    let mut interface = ParallelInterface::new_8bit(
+        pin(&lines, Role::Rs),
+        pin(&lines, Role::En),
+        pins(&lines),
+        NoDelay,
+    );
+    interface.init().unwrap();
+    interface.send_command(0x39).unwrap();
+    interface.send_data(&[0xa5]).unwrap();
+
+    assert_eq!(lines.borrow().strobes, [(false, 0x39), (true, 0xa5)]);
+}
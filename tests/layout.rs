@@ -0,0 +1,68 @@
+mod common;
+
+use st7032i::{Align, DisplaySize, Error};
+
+const SIZE: DisplaySize = DisplaySize::new(8, 2).unwrap();
+
+fn render(display: common::Display) -> String {
+    common::device(display).render()
+}
+
+#[test]
+fn write_at() {
+    let mut display = common::display(SIZE);
+    display.write_at(0, 5, "Clipped").unwrap();
+    display.write_at(1, 0, "Hi").unwrap();
+    assert_eq!(display.write_at(2, 0, "x"), Err(Error::OutOfRange));
+    assert_eq!(display.write_at(0, 8, "x"), Err(Error::OutOfRange));
+
+    let device = common::device(display);
+    assert_eq!(device.render(), "     Cli\nHi      ");
+    assert_eq!(device.ddram()[8], b' ');
+}
+
+#[test]
+fn write_aligned() {
+    let mut display = common::display(SIZE);
+    display.write_at(0, 0, "########").unwrap();
+    display.write_aligned(0, 1, 6, "ab", Align::Center).unwrap();
+    display.write_aligned(1, 0, 4, "ab", Align::Right).unwrap();
+    display.write_aligned(1, 4, 4, "c", Align::Left).unwrap();
+    assert_eq!(render(display), "#  ab  #\n  abc   ");
+}
+
+#[test]
+fn write_aligned_truncate() {
+    let mut display = common::display(SIZE);
+    display
+        .write_aligned(0, 0, 8, "Temperature", Align::Left)
+        .unwrap();
+    display
+        .write_aligned(1, 0, 3, "Temp", Align::Right)
+        .unwrap();
+    assert_eq!(
+        display.write_aligned(1, 4, 5, "x", Align::Left),
+        Err(Error::OutOfRange)
+    );
+    assert_eq!(render(display), "Tempe...\nTem     ");
+}
+
+#[test]
+fn write_wrapped() {
+    let mut display = common::display(SIZE);
+    display.write_wrapped(0, "Hello big").unwrap();
+    assert_eq!(render(display), "Hello   \nbig     ");
+
+    let mut display = common::display(SIZE);
+    display.write_wrapped(0, "Hello big world").unwrap();
+    assert_eq!(render(display), "Hello   \nbig  ...");
+
+    let mut display = common::display(SIZE);
+    display.write_wrapped(0, "Hi Supercalifragilistic").unwrap();
+    assert_eq!(render(display), "Hi      \nSuper...");
+
+    let mut display = common::display(SIZE);
+    display.write_at(0, 0, "keep").unwrap();
+    display.write_wrapped(1, "one two").unwrap();
+    assert_eq!(render(display), "keep    \none two ");
+}
@@ -0,0 +1,91 @@
+use core::cell::RefCell;
+use st7032i::bus::SharedI2c;
+use st7032i::interface::I2cInterface;
+use st7032i::mock::{Device, MockDelay};
+use st7032i::{DisplaySize, Error, Marquee, MarqueeMode, ST7032i};
+
+const SIZE: DisplaySize = DisplaySize::new(8, 2).unwrap();
+
+type Display<'a> = ST7032i<I2cInterface<SharedI2c<'a, Device>>, MockDelay>;
+
+fn display(device: &RefCell<Device>) -> Display<'_> {
+    let mut display = ST7032i::new(SharedI2c::new(device), MockDelay::default(), SIZE);
+    display.init().unwrap();
+    display.write_at(0, 0, "Fixed").unwrap();
+    display
+}
+
+/// Marquee area after each of `ticks` ticks
+fn frames(
+    device: &RefCell<Device>,
+    display: &mut Display,
+    marquee: &mut Marquee,
+    ticks: usize,
+) -> Vec<String> {
+    (0..ticks)
+        .map(|_| {
+            marquee.tick(display).unwrap();
+            let screen = device.borrow().render();
+            assert!(screen.starts_with("Fixed"));
+            screen.lines().nth(1).unwrap()[2..6].to_string()
+        })
+        .collect()
+}
+
+#[test]
+fn fits() {
+    let device = RefCell::new(Device::new(SIZE));
+    let mut display = display(&device);
+    let mut marquee = Marquee::new("abc", 1, 2, 4);
+    assert_eq!(
+        frames(&device, &mut display, &mut marquee, 3),
+        ["abc ", "abc ", "abc "]
+    );
+}
+
+#[test]
+fn scroll_loop() {
+    let device = RefCell::new(Device::new(SIZE));
+    let mut display = display(&device);
+    let mut marquee = Marquee::new("abcdef", 1, 2, 4).gap(1);
+    assert_eq!(
+        frames(&device, &mut display, &mut marquee, 9),
+        ["abcd", "bcde", "cdef", "def ", "ef a", "f ab", " abc", "abcd", "bcde"]
+    );
+}
+
+#[test]
+fn bounce_with_speed_and_pause() {
+    let device = RefCell::new(Device::new(SIZE));
+    let mut display = display(&device);
+    let mut marquee = Marquee::new("abcde", 1, 2, 4)
+        .mode(MarqueeMode::Bounce)
+        .speed(2)
+        .pause(1);
+    assert_eq!(
+        frames(&device, &mut display, &mut marquee, 9),
+        ["abcd", "abcd", "abcd", "bcde", "bcde", "bcde", "abcd", "abcd", "abcd"]
+    );
+}
+
+#[test]
+fn set_text() {
+    let device = RefCell::new(Device::new(SIZE));
+    let mut display = display(&device);
+    let mut marquee = Marquee::new("abcdef", 1, 2, 4);
+    marquee.tick(&mut display).unwrap();
+    marquee.tick(&mut display).unwrap();
+    marquee.set_text("xy");
+    assert_eq!(
+        frames(&device, &mut display, &mut marquee, 2),
+        ["xy  ", "xy  "]
+    );
+}
+
+#[test]
+fn out_of_range() {
+    let device = RefCell::new(Device::new(SIZE));
+    let mut display = display(&device);
+    let mut marquee = Marquee::new("abcdef", 1, 6, 4);
+    assert_eq!(marquee.tick(&mut display), Err(Error::OutOfRange));
+}
@@ -0,0 +1,293 @@
+mod common;
+
+use core::cell::RefCell;
+use core::fmt::Write;
+use st7032i::bus::{BorrowedI2c, SharedI2c};
+use st7032i::mock::{Device, MockDelay, MockError, Target};
+use st7032i::trace::{Event, Trace, TraceDelay, TraceI2c};
+use st7032i::{Config, Direction, DisplaySize, Error, ST7032i, Timing};
+
+const SIZE: DisplaySize = DisplaySize::new(8, 2).unwrap();
+
+#[test]
+fn init() {
+    let device = common::device(common::display(SIZE));
+    assert!(!device.extended());
+    assert!(device.two_lines());
+    assert!(device.display_on());
+    assert!(!device.cursor());
+    assert!(device.increment());
+    assert!(!device.autoscroll());
+    assert_eq!(device.target(), Target::Ddram);
+    assert_eq!(device.address_counter(), 0);
+    assert_eq!(device.render(), "        \n        ");
+}
+
+#[test]
+fn init_power_config() {
+    let mut display = ST7032i::with_config(
+        Device::new(SIZE),
+        MockDelay::default(),
+        SIZE,
+        Config::cog_3v3(),
+    );
+    display.init().unwrap();
+    let device = common::device(display);
+    assert!(!device.bias());
+    assert_eq!(device.osc_frequency(), 4);
+    assert!(device.booster());
+    assert!(device.follower());
+    assert_eq!(device.follower_ratio(), 4);
+    assert_eq!(device.contrast(), 32);
+}
+
+#[test]
+fn config_clamped() {
+    let config = Config::default()
+        .contrast(100)
+        .osc_frequency(9)
+        .follower(true, 12);
+    let mut display = ST7032i::with_config(Device::new(SIZE), MockDelay::default(), SIZE, config);
+    display.enable_icons().unwrap();
+    display.set_double_height(false).unwrap();
+    display.init().unwrap();
+    let device = common::device(display);
+    assert_eq!(device.contrast(), 63);
+    assert_eq!(device.osc_frequency(), 7);
+    assert_eq!(device.follower_ratio(), 7);
+    assert!(device.icons_enabled());
+}
+
+#[test]
+fn display_size() {
+    assert_eq!(DisplaySize::new(20, 4), None);
+    assert_eq!(DisplaySize::new(100, 1), None);
+    assert_eq!(DisplaySize::new(41, 2), None);
+    assert_eq!(DisplaySize::new(0, 1), None);
+    assert!(DisplaySize::new(80, 1).is_some());
+
+    let mut display = common::display(SIZE);
+    assert_eq!(display.move_cursor(2, 0), Err(Error::OutOfRange));
+    assert_eq!(display.move_cursor(1, 8), Err(Error::OutOfRange));
+    assert_eq!(display.move_cursor(1, 7), Ok(()));
+}
+
+#[test]
+fn write_and_move_cursor() {
+    let mut display = common::display(SIZE);
+    write!(display, "Hello").unwrap();
+    display.move_cursor(1, 3).unwrap();
+    write!(display, "°C").unwrap();
+    let device = common::device(display);
+    assert_eq!(device.render(), "Hello   \n   °C   ");
+    assert_eq!(device.visible_cursor(), Some((1, 5)));
+}
+
+#[test]
+fn clear_and_home() {
+    let mut display = common::display(SIZE);
+    write!(display, "abc").unwrap();
+    display.home().unwrap();
+    write!(display, "X").unwrap();
+    display.show_cursor(true).unwrap();
+    let device = common::device(display);
+    assert_eq!(device.render(), "Xbc     \n        ");
+    assert!(device.cursor());
+    assert!(device.blink());
+
+    let mut display = common::display(SIZE);
+    write!(display, "abc").unwrap();
+    display.clear().unwrap();
+    let device = common::device(display);
+    assert_eq!(device.render(), "        \n        ");
+    assert_eq!(device.address_counter(), 0);
+}
+
+#[test]
+fn line_wrap() {
+    let mut display = common::display(SIZE);
+    display.write_bytes(&[b'#'; 41]).unwrap();
+    let device = common::device(display);
+    assert_eq!(device.address_counter(), 0x41);
+    assert_eq!(device.render(), "########\n#       ");
+}
+
+#[test]
+fn create_char() {
+    let bitmap = [0x04, 0x0e, 0x1f, 0x04, 0x04, 0x04, 0x04, 0x00];
+    let mut display = common::display(SIZE);
+    display.create_char(3, bitmap).unwrap();
+    display.move_cursor(0, 0).unwrap();
+    display.write_bytes(&[3]).unwrap();
+    let device = common::device(display);
+    assert_eq!(device.glyph(3), bitmap);
+    assert_eq!(device.glyph(2), [0; 8]);
+    assert_eq!(device.ddram()[0], 3);
+}
+
+#[test]
+fn shift_display() {
+    let mut display = common::display(SIZE);
+    write!(display, "abcdefgh").unwrap();
+    display.shift_display(Direction::RightToLeft).unwrap();
+    display.shift_display(Direction::RightToLeft).unwrap();
+    let device = common::device(display);
+    assert_eq!(device.shift(), 2);
+    assert_eq!(device.render(), "cdefgh  \n        ");
+
+    let mut display = common::display(SIZE);
+    write!(display, "abc").unwrap();
+    display.shift_display(Direction::LeftToRigh).unwrap();
+    let device = common::device(display);
+    assert_eq!(device.render(), " abc    \n        ");
+}
+
+#[test]
+fn autoscroll() {
+    let mut display = common::display(SIZE);
+    display.move_cursor(0, 7).unwrap();
+    display.enable_scroll(Direction::LeftToRigh).unwrap();
+    write!(display, "abc").unwrap();
+    let device = common::device(display);
+    assert!(device.autoscroll());
+    assert_eq!(device.shift(), 3);
+    assert_eq!(device.render(), "    abc \n        ");
+}
+
+#[test]
+fn icons() {
+    let mut display = common::display(SIZE);
+    display.enable_icons().unwrap();
+    display.set_icon(2, 4, true).unwrap();
+    display.set_icon(15, 0, true).unwrap();
+    let device = common::device(display);
+    assert!(device.icons_enabled());
+    assert_eq!(device.icon_ram()[2], 0b_10000);
+    assert_eq!(device.icon_ram()[15], 0b_00001);
+    assert!(!device.extended());
+}
+
+#[test]
+fn contrast() {
+    let mut display = common::display(SIZE);
+    display.enable_icons().unwrap();
+    display.set_contrast(45).unwrap();
+    assert_eq!(display.set_contrast(64), Err(Error::InvalidArgument));
+    let device = common::device(display);
+    assert_eq!(device.contrast(), 45);
+    assert!(device.icons_enabled());
+    assert!(device.booster());
+    assert!(!device.extended());
+}
+
+#[test]
+fn double_height() {
+    let size = DisplaySize::new(16, 1).unwrap();
+    let mut display = ST7032i::with_config(
+        Device::new(size),
+        MockDelay::default(),
+        size,
+        Config::default().double_height(true),
+    );
+    display.init().unwrap();
+    write!(display, "Big").unwrap();
+    let device = common::device(display);
+    assert!(device.double_height());
+    assert!(!device.two_lines());
+    assert_eq!(device.render(), "Big             ");
+}
+
+#[test]
+fn double_height_at_runtime() {
+    let size = DisplaySize::new(16, 2).unwrap();
+    let device = RefCell::new(Device::new(size));
+    let mut display = ST7032i::new(SharedI2c::new(&device), MockDelay::default(), size);
+    display.init().unwrap();
+
+    display.set_double_height(true).unwrap();
+    assert_eq!(display.move_cursor(1, 0), Err(Error::OutOfRange));
+    display.move_cursor(0, 2).unwrap();
+    {
+        let device = device.borrow();
+        assert!(device.double_height());
+        assert!(!device.two_lines());
+        assert!(!device.extended());
+    }
+
+    display.set_double_height(false).unwrap();
+    display.move_cursor(1, 0).unwrap();
+    write!(display, "ok").unwrap();
+    let device = device.borrow();
+    assert!(!device.double_height());
+    assert!(device.two_lines());
+    assert_eq!(common::row(&device, 1)[..2], *b"ok");
+}
+
+#[test]
+fn wrong_address() {
+    let mut display =
+        ST7032i::new(Device::new(SIZE), MockDelay::default(), SIZE).with_address(0x3f);
+    assert_eq!(display.init(), Err(Error::Bus(MockError::Nack)));
+
+    let mut display = ST7032i::new(
+        Device::new(SIZE).with_address(0x3f),
+        MockDelay::default(),
+        SIZE,
+    )
+    .with_address(0x3f);
+    assert_eq!(display.init(), Ok(()));
+}
+
+#[test]
+fn buffered_flush() {
+    let mut buffered = st7032i::BufferedDisplay::new(common::display(SIZE));
+    write!(buffered, "Temp").unwrap();
+    buffered.move_cursor(1, 2).unwrap();
+    write!(buffered, "21.5°").unwrap();
+    buffered.flush().unwrap();
+    let device = common::device(buffered.release());
+    assert_eq!(device.render(), "Temp    \n  21.5° ");
+}
+
+#[test]
+fn delay() {
+    let (_, delay) = common::display(SIZE).release();
+    assert!(delay.elapsed_us() >= 40_000);
+}
+
+#[test]
+fn datasheet_timing() {
+    for &(osc, command_us, clear_us) in [(0, 40, 1620), (4, 27, 1080)].iter() {
+        let trace = Trace::new();
+        let config = Config::default()
+            .timing(Timing::Datasheet)
+            .osc_frequency(osc);
+        let mut display = ST7032i::with_config(
+            TraceI2c::new(Device::new(SIZE), &trace),
+            TraceDelay::new(MockDelay::default(), &trace),
+            SIZE,
+            config,
+        );
+        display.init().unwrap();
+        trace.clear();
+
+        display.move_cursor(1, 0).unwrap();
+        display.clear().unwrap();
+        let delays: Vec<Event> = trace
+            .events()
+            .into_iter()
+            .filter(|event| matches!(event, Event::Delay(_)))
+            .collect();
+        assert_eq!(delays, [Event::Delay(command_us), Event::Delay(clear_us)]);
+    }
+}
+
+#[test]
+fn borrowed_bus() {
+    let mut device = Device::new(SIZE);
+    let mut display = ST7032i::new(BorrowedI2c::new(&mut device), MockDelay::default(), SIZE);
+    display.init().unwrap();
+    write!(display, "Hi").unwrap();
+    display.release();
+    assert_eq!(device.render(), "Hi      \n        ");
+}
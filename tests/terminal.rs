@@ -0,0 +1,43 @@
+mod common;
+
+use core::fmt::Write;
+use st7032i::mock::{Device, MockDelay};
+use st7032i::terminal::{frame, Terminal};
+use st7032i::{DisplaySize, ST7032i};
+
+const SIZE: DisplaySize = DisplaySize::new(4, 2).unwrap();
+
+#[test]
+fn draw() {
+    let terminal = Terminal::new(Device::new(SIZE), Vec::new());
+    let mut display = ST7032i::new(terminal, MockDelay::default(), SIZE);
+    display.init().unwrap();
+    write!(display, "Hi").unwrap();
+
+    let (terminal, _) = display.release();
+    let (device, out) = terminal.release();
+    let out = String::from_utf8(out).unwrap();
+    let last = out.rfind("\x1b[9A").unwrap();
+    let redrawn: String = out[last + 4..].split("\x1b[2K").collect();
+    assert_eq!(redrawn, frame(&device));
+    assert!(redrawn.contains("H\x1b[0m\x1b[30;102mi\x1b[0m"));
+}
+
+#[test]
+fn glyphs() {
+    let mut display = common::display(SIZE);
+    display
+        .create_char(1, [0x1f, 0x11, 0x11, 0x11, 0x11, 0x11, 0x11, 0x1f])
+        .unwrap();
+    display.move_cursor(0, 0).unwrap();
+    display.write_bytes(&[1]).unwrap();
+    display.show_cursor(false).unwrap();
+
+    let frame = frame(&common::device(display));
+    let lines: Vec<&str> = frame.lines().collect();
+    assert_eq!(lines.len(), 9);
+    assert!(lines[1].starts_with("│\x1b[30;102m\x1b[35;102m1\x1b[0m\x1b[30;102m\x1b[4m \x1b[0m"));
+    assert!(lines[5].starts_with("····· █▀▀▀█ "));
+    assert!(lines[6].starts_with("····· █···█ "));
+    assert!(lines[8].starts_with("····· █▄▄▄█ "));
+}